@@ -1,20 +1,138 @@
 use actix_web::{post, web, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
-use crate::text_analyzer::TextAnalyzer;
-use crate::web_analyzer::WebAnalyzer;
+use crate::collocation::Association;
+use crate::entity::{EntityKind, EntityMode};
+use crate::inclusive::InclusiveMode;
+use crate::language::{detect_language, Language};
+use crate::links::{Link, LinkStats};
+use crate::metadata::PageMetadata;
+use crate::normalizer::UnicodeForm;
+use crate::pos::{PosPattern, PosTagger};
+use crate::readability::reading_ease_formula;
+use crate::stemmer::{NormalizationMode, Stemmer};
+use crate::subsumption::{find_subsumed, SubsumptionMode};
+use crate::text_analyzer::{Keyphrase, StopWordPolicy, SurfaceForms, TextAnalyzer};
+use crate::tokenizer::TokenizerKind;
+use crate::web_analyzer::{ContentExtraction, Heading, WebAnalyzer, WebPage};
+use crate::zone::{Zone, ZoneCounts, ZoneWeights};
 use std::error::Error;
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Nombre d'analyses conservées en mémoire pour le concordancier
+const MAX_STORED_ANALYSES: usize = 50;
+
+// Nombre de thèmes de titres renvoyés
+const MAX_HEADING_TOPICS: usize = 100;
+
+// Nombre de mots de contexte par défaut de chaque côté d'une concordance
+const DEFAULT_CONCORDANCE_CONTEXT: usize = 5;
+
+// Nombre maximal de mots de contexte accepté par le concordancier
+const MAX_CONCORDANCE_CONTEXT: usize = 100;
 
 // Structures de requête et réponse
 #[derive(Deserialize)]
 pub struct AnalysisRequest {
     urls: Vec<String>,
+    content_extraction: Option<ContentExtraction>,
+    // Zone de la page analysée à la place du contenu (« anchors » pour le texte des liens)
+    analysis_zone: Option<Zone>,
     ngrams_to_analyze: Option<Vec<usize>>,
+    tokenizer: Option<TokenizerKind>,
+    normalization: Option<NormalizationMode>,
+    unicode_normalization: Option<UnicodeForm>,
+    fold_diacritics: Option<bool>,
+    language: Option<Language>,
+    sort_by: Option<SortKey>,
+    // Seuils d'association, appliqués aux expressions de plusieurs mots
+    min_pmi: Option<f64>,
+    min_log_likelihood: Option<f64>,
+    min_t_score: Option<f64>,
+    extraction_mode: Option<ExtractionMode>,
+    stop_word_policy: Option<StopWordPolicy>,
+    drop_numeric_tokens: Option<bool>,
+    entity_mode: Option<EntityMode>,
+    inclusive_writing: Option<InclusiveMode>,
+    // Motifs morphosyntaxiques autorisés par taille de n-gramme (« NOUN ADJ »)
+    pos_patterns: Option<HashMap<usize, Vec<PosPattern>>>,
+    // Ajustements de la liste de mots vides, propres à cette requête
+    extra_stop_words: Option<Vec<String>>,
+    remove_stop_words: Option<Vec<String>>,
+    protected_terms: Option<Vec<String>>,
+    // Ratio à partir duquel une expression est expliquée par une expression plus longue
+    subsumption_threshold: Option<f64>,
+    subsumption_mode: Option<SubsumptionMode>,
+    // Poids de chaque zone de la page dans `zone_score`
+    zone_weights: Option<HashMap<Zone, f64>>,
+    // Conserve les pages analysées pour le concordancier
+    store_analysis: Option<bool>,
+}
+
+impl AnalysisRequest {
+    // Réglages incohérents, refusés avant toute analyse
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if let Some(threshold) = self.subsumption_threshold {
+            if !(threshold > 0.0 && threshold <= 1.0) {
+                return Err(format!("subsumption_threshold doit être compris entre 0 (exclu) et 1 : {}", threshold));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Requête du concordancier : des URLs ou une analyse conservée
+#[derive(Deserialize)]
+pub struct ConcordanceRequest {
+    urls: Option<Vec<String>>,
+    analysis_id: Option<String>,
+    expression: String,
+    context_size: Option<usize>,
+    content_extraction: Option<ContentExtraction>,
+    tokenizer: Option<TokenizerKind>,
+    normalization: Option<NormalizationMode>,
+    unicode_normalization: Option<UnicodeForm>,
+    fold_diacritics: Option<bool>,
+    language: Option<Language>,
+}
+
+// Méthode d'extraction des expressions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractionMode {
+    // N-grammes de taille fixe (`ngrams_to_analyze`)
+    #[default]
+    Ngrams,
+    // Expressions de longueur variable extraites par RAKE
+    Rake,
+    // Mots clés classés par PageRank sur le graphe de co-occurrence, fusionnés en expressions
+    #[serde(rename = "textrank")]
+    TextRank,
+}
+
+// Critère de classement des expressions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    #[default]
+    DocCount,
+    Occurrences,
+    TfIdf,
+    Pmi,
+    LogLikelihood,
+    TScore,
+    Score,
+    ZoneScore,
 }
 
 #[derive(Serialize)]
 pub struct FrequencyResult {
     expression: String,
+    // Forme écrite la plus fréquente (« Paris », « CAPES »)
+    display_form: String,
     gram_type: String,
     #[serde(serialize_with = "serialize_f64_2_decimals")]
     average_occurrences: f64,
@@ -25,16 +143,112 @@ pub struct FrequencyResult {
     #[serde(serialize_with = "serialize_f64_2_decimals")]
     average_doc_size: f64,
     sources: Vec<String>,
+    // TF-IDF le plus élevé parmi les documents, puis le détail par document
+    #[serde(serialize_with = "serialize_f64_4_decimals")]
+    tf_idf: f64,
+    tf_idf_by_source: Vec<SourceScore>,
+    // Mesures d'association moyennes, absentes pour les mots seuls
+    #[serde(serialize_with = "serialize_option_f64_2_decimals", skip_serializing_if = "Option::is_none")]
+    pmi: Option<f64>,
+    #[serde(serialize_with = "serialize_option_f64_2_decimals", skip_serializing_if = "Option::is_none")]
+    log_likelihood: Option<f64>,
+    #[serde(serialize_with = "serialize_option_f64_2_decimals", skip_serializing_if = "Option::is_none")]
+    t_score: Option<f64>,
+    // Score moyen attribué par l'extracteur d'expressions clés
+    #[serde(serialize_with = "serialize_option_f64_2_decimals", skip_serializing_if = "Option::is_none")]
+    score: Option<f64>,
+    // Occurrences par zone de la page, puis moyenne pondérée par document
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    zone_occurrences: BTreeMap<Zone, usize>,
+    #[serde(serialize_with = "serialize_f64_2_decimals")]
+    zone_score: f64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    surface_forms: Vec<SurfaceForm>,
+    // Expression plus longue qui explique la quasi-totalité des occurrences
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subsumed_by: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SourceScore {
+    url: String,
+    #[serde(serialize_with = "serialize_f64_4_decimals")]
+    tf_idf: f64,
+}
+
+// Forme rencontrée dans les pages et fusionnée sous une expression normalisée
+#[derive(Serialize)]
+pub struct SurfaceForm {
+    form: String,
+    occurrences: usize,
 }
 
 #[derive(Serialize)]
 pub struct DocumentStats {
     url: String,
+    language: Language,
     total_retained: usize,
     total_unique: usize,
     word_count: usize,
+    sentence_count: usize,
     #[serde(serialize_with = "serialize_f64_2_decimals")]
     average_word_length: f64,
+    readability: ReadabilityStats,
+    entities: EntityStats,
+    // Plan H1–H6 de la page
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    outline: Vec<Heading>,
+    metadata: PageMetadata,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    links: Vec<Link>,
+    link_stats: LinkStats,
+}
+
+// Type schema.org et pages qui le déclarent
+#[derive(Serialize)]
+pub struct SchemaTypeUsage {
+    schema_type: String,
+    #[serde(serialize_with = "serialize_f64_2_decimals")]
+    doc_count_percentage: f64,
+    sources: Vec<String>,
+}
+
+// Nombres, montants, dates, pourcentages et mesures relevés dans la page
+#[derive(Serialize)]
+pub struct EntityStats {
+    numbers: usize,
+    prices: usize,
+    dates: usize,
+    percentages: usize,
+    measurements: usize,
+}
+
+// Lisibilité : Flesch (anglais) ou Kandel–Moles (français), et niveau scolaire Flesch–Kincaid
+#[derive(Serialize)]
+pub struct ReadabilityStats {
+    formula: String,
+    #[serde(serialize_with = "serialize_f64_2_decimals")]
+    reading_ease: f64,
+    #[serde(serialize_with = "serialize_f64_2_decimals")]
+    grade_level: f64,
+    #[serde(serialize_with = "serialize_f64_2_decimals")]
+    words_per_sentence: f64,
+    #[serde(serialize_with = "serialize_f64_2_decimals")]
+    syllables_per_word: f64,
+}
+
+// Titre rencontré dans plusieurs pages, une fois retirés les mots vides
+// (« Les épreuves orales » et « Épreuves orales » → « épreuves orales »)
+#[derive(Serialize)]
+pub struct HeadingTopic {
+    topic: String,
+    // Titre le plus fréquent tel qu'il est écrit
+    display_form: String,
+    levels: Vec<u8>,
+    occurrences: usize,
+    #[serde(serialize_with = "serialize_f64_2_decimals")]
+    doc_count_percentage: f64,
+    sources: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -48,9 +262,186 @@ pub struct UrlStatus {
 pub struct AnalysisResponse {
     frequencies: Vec<FrequencyResult>,
     document_stats: Vec<DocumentStats>,
+    heading_topics: Vec<HeadingTopic>,
+    schema_types: Vec<SchemaTypeUsage>,
+    url_statuses: Vec<UrlStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    analysis_id: Option<String>,
+}
+
+// Occurrence d'une expression dans une page, avec son contexte
+#[derive(Serialize)]
+pub struct ConcordanceResult {
+    url: String,
+    token_offset: usize,
+    left: String,
+    keyword: String,
+    right: String,
+}
+
+#[derive(Serialize)]
+pub struct ConcordanceResponse {
+    expression: String,
+    lines: Vec<ConcordanceResult>,
     url_statuses: Vec<UrlStatus>,
 }
 
+// Page analysée, conservée pour les requêtes suivantes
+#[derive(Debug, Clone)]
+pub(crate) struct StoredDocument {
+    pub(crate) url: String,
+    pub(crate) content: String,
+    pub(crate) language: Language,
+    pub(crate) zones: HashMap<Zone, String>,
+    pub(crate) outline: Vec<Heading>,
+    pub(crate) metadata: PageMetadata,
+    pub(crate) links: Vec<Link>,
+}
+
+#[derive(Debug, Clone)]
+struct StoredAnalysis {
+    documents: Vec<StoredDocument>,
+    options: AnalysisOptions,
+}
+
+/// In-memory store of the latest analyses, shared between requests.
+///
+/// Only the `MAX_STORED_ANALYSES` most recent analyses are kept.
+#[derive(Debug, Default)]
+pub struct AnalysisStore {
+    analyses: Mutex<HashMap<String, StoredAnalysis>>,
+    order: Mutex<VecDeque<String>>,
+    counter: AtomicU64,
+}
+
+impl AnalysisStore {
+    fn insert(&self, analysis: StoredAnalysis) -> String {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos());
+        let id = format!("{:x}-{:x}", nanos, self.counter.fetch_add(1, Ordering::Relaxed));
+
+        let mut analyses = self.analyses.lock().unwrap();
+        let mut order = self.order.lock().unwrap();
+        analyses.insert(id.clone(), analysis);
+        order.push_back(id.clone());
+        while order.len() > MAX_STORED_ANALYSES {
+            if let Some(oldest) = order.pop_front() {
+                analyses.remove(&oldest);
+            }
+        }
+        id
+    }
+
+    fn get(&self, id: &str) -> Option<StoredAnalysis> {
+        self.analyses.lock().unwrap().get(id).cloned()
+    }
+}
+
+// Options d'analyse communes à toutes les URLs d'une requête
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AnalysisOptions {
+    ngrams: Vec<usize>,
+    content_extraction: ContentExtraction,
+    analysis_zone: Option<Zone>,
+    tokenizer: TokenizerKind,
+    normalization: NormalizationMode,
+    unicode_normalization: UnicodeForm,
+    fold_diacritics: bool,
+    // Langue imposée par la requête, sinon détectée pour chaque document
+    language: Option<Language>,
+    sort_by: SortKey,
+    min_pmi: Option<f64>,
+    min_log_likelihood: Option<f64>,
+    min_t_score: Option<f64>,
+    extraction_mode: ExtractionMode,
+    stop_word_policy: StopWordPolicy,
+    drop_numeric_tokens: bool,
+    entity_mode: EntityMode,
+    inclusive_writing: InclusiveMode,
+    pos_patterns: HashMap<usize, Vec<PosPattern>>,
+    extra_stop_words: Vec<String>,
+    remove_stop_words: Vec<String>,
+    protected_terms: Vec<String>,
+    subsumption_threshold: Option<f64>,
+    subsumption_mode: SubsumptionMode,
+    zone_weights: ZoneWeights,
+}
+
+impl AnalysisOptions {
+    pub(crate) fn from_request(request: &AnalysisRequest) -> Self {
+        let extraction_mode = request.extraction_mode.unwrap_or_default();
+        // Les expressions clés sont classées par défaut selon leur score
+        let default_sort = match extraction_mode {
+            ExtractionMode::Ngrams => SortKey::DocCount,
+            ExtractionMode::Rake | ExtractionMode::TextRank => SortKey::Score,
+        };
+
+        AnalysisOptions {
+            ngrams: request.ngrams_to_analyze.clone().unwrap_or(vec![1, 2, 3]),
+            content_extraction: request.content_extraction.unwrap_or_default(),
+            analysis_zone: request.analysis_zone,
+            tokenizer: request.tokenizer.unwrap_or_default(),
+            normalization: request.normalization.unwrap_or_default(),
+            unicode_normalization: request.unicode_normalization.unwrap_or_default(),
+            fold_diacritics: request.fold_diacritics.unwrap_or(false),
+            language: request.language,
+            sort_by: request.sort_by.unwrap_or(default_sort),
+            min_pmi: request.min_pmi,
+            min_log_likelihood: request.min_log_likelihood,
+            min_t_score: request.min_t_score,
+            extraction_mode,
+            stop_word_policy: request.stop_word_policy.unwrap_or_default(),
+            drop_numeric_tokens: request.drop_numeric_tokens.unwrap_or(false),
+            entity_mode: request.entity_mode.unwrap_or_default(),
+            inclusive_writing: request.inclusive_writing.unwrap_or_default(),
+            pos_patterns: request.pos_patterns.clone().unwrap_or_default(),
+            extra_stop_words: request.extra_stop_words.clone().unwrap_or_default(),
+            remove_stop_words: request.remove_stop_words.clone().unwrap_or_default(),
+            protected_terms: request.protected_terms.clone().unwrap_or_default(),
+            subsumption_threshold: request.subsumption_threshold,
+            subsumption_mode: request.subsumption_mode.unwrap_or_default(),
+            zone_weights: ZoneWeights::new(request.zone_weights.clone().unwrap_or_default()),
+        }
+    }
+
+    // Seules les options qui changent les tokens comptent pour le concordancier
+    fn from_concordance_request(request: &ConcordanceRequest) -> Self {
+        AnalysisOptions {
+            content_extraction: request.content_extraction.unwrap_or_default(),
+            tokenizer: request.tokenizer.unwrap_or_default(),
+            normalization: request.normalization.unwrap_or_default(),
+            unicode_normalization: request.unicode_normalization.unwrap_or_default(),
+            fold_diacritics: request.fold_diacritics.unwrap_or(false),
+            language: request.language,
+            ..Default::default()
+        }
+    }
+}
+
+// Analyseurs configurés pour une requête, un par langue et sans contenu : les
+// mots vides, les élisions et le lexique ne sont lus qu'une fois
+#[derive(Debug, Default)]
+pub(crate) struct AnalyzerCache {
+    templates: HashMap<Language, TextAnalyzer>,
+}
+
+impl AnalyzerCache {
+    // Analyseur du contenu, prêt pour le comptage
+    fn analyzer(
+        &mut self,
+        content: &str,
+        language: Language,
+        options: &AnalysisOptions,
+    ) -> Result<TextAnalyzer, Box<dyn Error>> {
+        let template = match self.templates.entry(language) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(configure_analyzer(language, options)?),
+        };
+        Ok(prepare_analyzer(template.with_content(content), options))
+    }
+}
+
 // Structure pour stocker les informations du document
 #[derive(Debug)]
 struct DocumentInfo {
@@ -58,13 +449,51 @@ struct DocumentInfo {
     word_count: usize,
 }
 
+// Cumul des occurrences d'une expression sur l'ensemble des documents
+#[derive(Debug, Default)]
+pub(crate) struct AggregatedFrequency {
+    total_count: f64,
+    total_percentage: f64,
+    sources: Vec<String>,
+    doc_sizes: Vec<usize>,
+    // Occurrences dans chaque document, dans l'ordre de `sources`
+    counts: Vec<usize>,
+    surface_forms: HashMap<String, usize>,
+    // Somme des mesures d'association et nombre de documents concernés
+    association_total: Association,
+    association_docs: usize,
+    score_total: f64,
+    score_docs: usize,
+    zone_counts: ZoneCounts,
+}
+
+// Clé d'agrégation : (expression, type de n-gramme)
+pub(crate) type FrequencyMap = HashMap<(String, String), AggregatedFrequency>;
+
+// Résultats d'une taille de n-gramme pour un document
+struct NgramResults<'a> {
+    frequencies: &'a HashMap<String, usize>,
+    percentages: &'a HashMap<String, f64>,
+    surface_forms: Option<&'a SurfaceForms>,
+    associations: &'a HashMap<String, Association>,
+    scores: &'a HashMap<String, f64>,
+    zones: &'a HashMap<String, ZoneCounts>,
+}
+
 // Point d'entrée de l'API
 #[post("/api/analyze")]
-pub async fn analyze_urls(data: web::Json<AnalysisRequest>) -> impl Responder {
+pub async fn analyze_urls(
+    data: web::Json<AnalysisRequest>,
+    store: web::Data<AnalysisStore>,
+) -> impl Responder {
+    if let Err(message) = data.validate() {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": message }));
+    }
     let urls = data.urls.clone();
-    let ngrams = data.ngrams_to_analyze.clone().unwrap_or(vec![1, 2, 3]);
+    let options = AnalysisOptions::from_request(&data);
+    let store = data.store_analysis.unwrap_or(false).then_some(store.get_ref());
     
-    match analyze_content(urls, ngrams).await {
+    match analyze_content(urls, options, store).await {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
             "error": e.to_string()
@@ -72,18 +501,41 @@ pub async fn analyze_urls(data: web::Json<AnalysisRequest>) -> impl Responder {
     }
 }
 
+#[post("/api/concordance")]
+pub async fn concordance(
+    data: web::Json<ConcordanceRequest>,
+    store: web::Data<AnalysisStore>,
+) -> impl Responder {
+    match concordance_content(&data, &store).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(ConcordanceError::Request(message)) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": message
+        })),
+        Err(ConcordanceError::Server(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        }))
+    }
+}
+
 // Fonctions d'analyse
-async fn analyze_content(urls: Vec<String>, ngrams: Vec<usize>) -> Result<AnalysisResponse, Box<dyn Error>> {
+async fn analyze_content(
+    urls: Vec<String>,
+    options: AnalysisOptions,
+    store: Option<&AnalysisStore>,
+) -> Result<AnalysisResponse, Box<dyn Error>> {
     let mut frequencies = HashMap::new();
     let mut doc_stats = Vec::new();
     let mut url_statuses = Vec::new();
+    let mut documents = Vec::new();
     let mut successful_urls = 0;
+    let mut analyzers = AnalyzerCache::default();
     
     for url in &urls {
-        match analyze_single_url(url, &ngrams, &mut frequencies, &mut doc_stats).await {
-            Ok(_) => {
+        match analyze_single_url(url, &options, &mut analyzers, &mut frequencies, &mut doc_stats).await {
+            Ok(document) => {
                 successful_urls += 1;
                 url_statuses.push(create_url_status(url, true, None));
+                documents.push(document);
             }
             Err(e) => url_statuses.push(create_url_status(url, false, Some(e.to_string()))),
         }
@@ -93,114 +545,504 @@ async fn analyze_content(urls: Vec<String>, ngrams: Vec<usize>) -> Result<Analys
         return Err("Aucune URL n'a pu être analysée".into());
     }
 
-    let results = process_frequencies(frequencies, successful_urls);
+    let protected = protected_expressions(&documents, &options, &mut analyzers);
+    let results = process_frequencies(frequencies, successful_urls, &options, &protected);
+    let heading_topics = aggregate_heading_topics(&documents, successful_urls, &options, &mut analyzers);
+    let schema_types = aggregate_schema_types(&documents, successful_urls);
+    let analysis_id = store.map(|store| store.insert(StoredAnalysis { documents, options }));
 
     Ok(AnalysisResponse {
         frequencies: results,
         document_stats: doc_stats,
+        heading_topics,
+        schema_types,
+        url_statuses,
+        analysis_id,
+    })
+}
+
+// Erreur du concordancier : requête à corriger (400) ou échec du serveur (500)
+#[derive(Debug)]
+enum ConcordanceError {
+    Request(String),
+    Server(Box<dyn Error>),
+}
+
+impl From<Box<dyn Error>> for ConcordanceError {
+    fn from(error: Box<dyn Error>) -> Self {
+        ConcordanceError::Server(error)
+    }
+}
+
+async fn concordance_content(
+    request: &ConcordanceRequest,
+    store: &AnalysisStore,
+) -> Result<ConcordanceResponse, ConcordanceError> {
+    let context = request
+        .context_size
+        .unwrap_or(DEFAULT_CONCORDANCE_CONTEXT)
+        .min(MAX_CONCORDANCE_CONTEXT);
+    if !request.expression.chars().any(char::is_alphanumeric) {
+        return Err(ConcordanceError::Request(format!(
+            "L'expression ne contient aucun mot : {}",
+            request.expression
+        )));
+    }
+    let mut url_statuses = Vec::new();
+
+    // Pages d'une analyse conservée, sinon pages téléchargées pour l'occasion
+    let (documents, options) = match (&request.analysis_id, &request.urls) {
+        (Some(id), _) => {
+            let analysis = store
+                .get(id)
+                .ok_or_else(|| ConcordanceError::Request(format!("Analyse inconnue ou expirée : {}", id)))?;
+            (analysis.documents, analysis.options)
+        }
+        (None, Some(urls)) => {
+            let options = AnalysisOptions::from_concordance_request(request);
+            let mut documents = Vec::new();
+            for url in urls {
+                match fetch_document(url, &options).await {
+                    Ok(document) => {
+                        url_statuses.push(create_url_status(url, true, None));
+                        documents.push(document);
+                    }
+                    Err(e) => url_statuses.push(create_url_status(url, false, Some(e.to_string()))),
+                }
+            }
+            (documents, options)
+        }
+        (None, None) => {
+            return Err(ConcordanceError::Request(
+                "Il faut fournir des URLs ou un identifiant d'analyse".to_string(),
+            ))
+        }
+    };
+
+    let mut lines = Vec::new();
+    let mut analyzers = AnalyzerCache::default();
+    for document in &documents {
+        // L'expression passe par le même traitement que le contenu
+        let mut expression = analyzers.analyzer(&request.expression, document.language, &options)?;
+        let keys: Vec<String> = expression.tokens().iter().map(|token| token.text.clone()).collect();
+
+        let mut analyzer = analyzers.analyzer(&document.content, document.language, &options)?;
+        lines.extend(
+            analyzer
+                .concordance(&keys, context)
+                .into_iter()
+                .map(|line| ConcordanceResult {
+                    url: document.url.clone(),
+                    token_offset: line.token_offset,
+                    left: line.left,
+                    keyword: line.keyword,
+                    right: line.right,
+                }),
+        );
+    }
+
+    Ok(ConcordanceResponse {
+        expression: request.expression.clone(),
+        lines,
         url_statuses,
     })
 }
 
 async fn analyze_single_url(
     url: &str,
-    ngrams: &[usize],
-    frequencies: &mut HashMap<(String, String), (f64, f64, Vec<String>, Vec<usize>)>,
+    options: &AnalysisOptions,
+    analyzers: &mut AnalyzerCache,
+    frequencies: &mut FrequencyMap,
+    doc_stats: &mut Vec<DocumentStats>,
+) -> Result<StoredDocument, Box<dyn Error>> {
+    let document = fetch_document(url, options).await?;
+    analyze_document(&document, options, analyzers, frequencies, doc_stats)?;
+    Ok(document)
+}
+
+// Compte les expressions d'une page téléchargée et relève ses statistiques
+pub(crate) fn analyze_document(
+    document: &StoredDocument,
+    options: &AnalysisOptions,
+    analyzers: &mut AnalyzerCache,
+    frequencies: &mut FrequencyMap,
     doc_stats: &mut Vec<DocumentStats>,
 ) -> Result<(), Box<dyn Error>> {
-    let content = fetch_and_prepare_content(url).await?;
-    let mut analyzer = create_analyzer(&content)?;
+    let language = document.language;
+    // Une zone choisie remplace le contenu de la page pour le comptage
+    let content = match options.analysis_zone {
+        Some(zone) => document.zones.get(&zone).map_or("", String::as_str),
+        None => document.content.as_str(),
+    };
+    let mut analyzer = analyzers.analyzer(content, language, options)?;
+    analyzer.set_sentence_bounded(options.analysis_zone.is_some());
+    for (zone, text) in &document.zones {
+        analyzer.add_zone(*zone, analyzers.analyzer(text, language, options)?);
+    }
     
     let doc_info = DocumentInfo {
-        url: url.to_string(),
+        url: document.url.clone(),
         word_count: analyzer.count_words(),
     };
     
-    process_ngrams(&mut analyzer, ngrams, &doc_info, frequencies);
-    collect_document_stats(&mut analyzer, url, doc_stats);
+    match options.extraction_mode {
+        ExtractionMode::Ngrams => process_ngrams(&mut analyzer, &options.ngrams, &doc_info, frequencies),
+        ExtractionMode::Rake => {
+            let keyphrases = analyzer.rake_keyphrases();
+            process_keyphrases(&mut analyzer, &keyphrases, &doc_info, frequencies);
+        }
+        ExtractionMode::TextRank => {
+            let keyphrases = analyzer.textrank_keyphrases();
+            process_keyphrases(&mut analyzer, &keyphrases, &doc_info, frequencies);
+        }
+    }
+    collect_document_stats(&mut analyzer, document, doc_stats);
     
     Ok(())
 }
 
 // Fonctions utilitaires
-async fn fetch_and_prepare_content(url: &str) -> Result<String, Box<dyn Error>> {
+async fn fetch_and_prepare_content(url: &str, extraction: ContentExtraction) -> Result<WebPage, Box<dyn Error>> {
     let mut web_analyzer = WebAnalyzer::new(url);
-    Ok(web_analyzer.fetch_and_analyze().await?)
+    web_analyzer.set_extraction(extraction);
+    web_analyzer.fetch_and_analyze().await
+}
+
+// Télécharge une page et détermine sa langue
+async fn fetch_document(url: &str, options: &AnalysisOptions) -> Result<StoredDocument, Box<dyn Error>> {
+    let page = fetch_and_prepare_content(url, options.content_extraction).await?;
+    let language = options
+        .language
+        .unwrap_or_else(|| detect_language(&page.content, page.lang.as_deref()));
+    Ok(StoredDocument {
+        url: url.to_string(),
+        content: page.content,
+        language,
+        zones: page.zones,
+        outline: page.outline,
+        metadata: page.metadata,
+        links: page.links,
+    })
+}
+
+// Réglages de la requête et fichiers de la langue, sans contenu
+fn configure_analyzer(
+    language: Language,
+    options: &AnalysisOptions,
+) -> Result<TextAnalyzer, Box<dyn Error>> {
+    let mut analyzer = TextAnalyzer::new("", language.stop_words_path())?;
+    // La ponctuation est conservée : le tokenizer se charge de la découpe
+    analyzer.set_tokenizer(options.tokenizer.build());
+    if options.normalization == NormalizationMode::Stem {
+        analyzer.set_stemmer(Some(Stemmer::new(language)));
+    }
+    analyzer.set_fold_diacritics(options.fold_diacritics);
+    analyzer.set_stop_word_policy(options.stop_word_policy);
+    analyzer.set_drop_numeric_tokens(options.drop_numeric_tokens);
+    analyzer.set_entity_mode(options.entity_mode);
+    analyzer.set_inclusive_mode(options.inclusive_writing);
+    if !options.pos_patterns.is_empty() {
+        analyzer.set_pos_tagger(Some(PosTagger::load(language)?));
+        analyzer.set_pos_patterns(options.pos_patterns.clone());
+    }
+    analyzer.customize_stop_words(
+        &options.extra_stop_words,
+        &options.remove_stop_words,
+        &options.protected_terms,
+    );
+    Ok(analyzer)
 }
 
-fn create_analyzer(content: &str) -> Result<TextAnalyzer, Box<dyn Error>> {
-    let mut analyzer = TextAnalyzer::new(content, "stop_words_french.txt")?;
+// Normalise le contenu d'un analyseur configuré et compte ses mots
+fn prepare_analyzer(mut analyzer: TextAnalyzer, options: &AnalysisOptions) -> TextAnalyzer {
+    analyzer.normalize_unicode(options.unicode_normalization);
     analyzer.analyze();
-    analyzer.remove_special_characters();
     analyzer.normalize_apostrophes();
     analyzer.clean_word();
+    analyzer.normalize_inclusive_writing();
     analyzer.count_words();
-    Ok(analyzer)
+    analyzer
 }
 
 fn process_ngrams(
     analyzer: &mut TextAnalyzer,
     ngrams: &[usize],
     doc_info: &DocumentInfo,
-    frequencies: &mut HashMap<(String, String), (f64, f64, Vec<String>, Vec<usize>)>,
+    frequencies: &mut FrequencyMap,
 ) {
     for &n in ngrams {
         analyzer.word_frequency_ngrams(n);
+        let associations = analyzer.collocation_scores(n);
+        let zones = analyzer.zone_frequencies(n);
         
         if let Some((freq_map, percent_map)) = analyzer._get_ngram_frequency(n) {
-            let gram_type = get_gram_type(n);
-            update_frequencies(freq_map, percent_map, gram_type, doc_info, frequencies);
+            let results = NgramResults {
+                frequencies: freq_map,
+                percentages: percent_map,
+                surface_forms: analyzer.get_surface_forms(n),
+                associations: &associations,
+                scores: &HashMap::new(),
+                zones: &zones,
+            };
+            update_frequencies(&results, &get_gram_type(n), doc_info, frequencies);
         }
     }
 }
 
-fn get_gram_type(n: usize) -> &'static str {
+// Expressions clés d'un document ayant le même nombre de mots
+#[derive(Default)]
+struct KeyphraseGroup {
+    counts: HashMap<String, usize>,
+    percentages: HashMap<String, f64>,
+    scores: HashMap<String, f64>,
+    surface_forms: SurfaceForms,
+}
+
+// Les expressions clés sont regroupées par nombre de mots pour garder le format des n-grammes
+fn process_keyphrases(
+    analyzer: &mut TextAnalyzer,
+    keyphrases: &[Keyphrase],
+    doc_info: &DocumentInfo,
+    frequencies: &mut FrequencyMap,
+) {
+    let mut groups: HashMap<usize, KeyphraseGroup> = HashMap::new();
+    for keyphrase in keyphrases {
+        let group = groups.entry(keyphrase.phrase.split(' ').count()).or_default();
+        let phrase = keyphrase.phrase.clone();
+        group.counts.insert(phrase.clone(), keyphrase.occurrences);
+        group.percentages.insert(
+            phrase.clone(),
+            keyphrase.occurrences as f64 * 100.0 / doc_info.word_count.max(1) as f64,
+        );
+        group.scores.insert(phrase.clone(), keyphrase.score);
+        group.surface_forms.insert(phrase, keyphrase.surface_forms.clone());
+    }
+
+    for (n, group) in &groups {
+        let zones = analyzer.zone_frequencies(*n);
+        let results = NgramResults {
+            frequencies: &group.counts,
+            percentages: &group.percentages,
+            surface_forms: Some(&group.surface_forms),
+            associations: &HashMap::new(),
+            scores: &group.scores,
+            zones: &zones,
+        };
+        update_frequencies(&results, &get_gram_type(*n), doc_info, frequencies);
+    }
+}
+
+fn get_gram_type(n: usize) -> String {
     match n {
-        1 => "mot",
-        2 => "bigramme",
-        3 => "trigramme",
-        4 => "quadrigramme",
-        5 => "pentagramme",
-        _ => "inconnu",
+        1 => "mot".to_string(),
+        2 => "bigramme".to_string(),
+        3 => "trigramme".to_string(),
+        4 => "quadrigramme".to_string(),
+        5 => "pentagramme".to_string(),
+        _ => format!("{}-gramme", n),
     }
 }
 
 fn update_frequencies(
-    freq_map: &HashMap<String, usize>,
-    percent_map: &HashMap<String, f64>,
+    results: &NgramResults,
     gram_type: &str,
     doc_info: &DocumentInfo,
-    frequencies: &mut HashMap<(String, String), (f64, f64, Vec<String>, Vec<usize>)>,
+    frequencies: &mut FrequencyMap,
 ) {
-    for (expr, count) in freq_map {
-        let percentage = *percent_map.get(expr).unwrap_or(&0.0);
+    for (expr, count) in results.frequencies {
+        let percentage = *results.percentages.get(expr).unwrap_or(&0.0);
         let entry = frequencies
             .entry((expr.clone(), gram_type.to_string()))
-            .or_insert((0.0, 0.0, Vec::new(), Vec::new()));
+            .or_default();
         
-        entry.0 += *count as f64;
-        entry.1 += percentage;
-        if !entry.2.contains(&doc_info.url) {
-            entry.2.push(doc_info.url.clone());
-            entry.3.push(doc_info.word_count);
+        entry.total_count += *count as f64;
+        entry.total_percentage += percentage;
+        match entry.sources.iter().position(|source| *source == doc_info.url) {
+            Some(index) => entry.counts[index] += count,
+            None => {
+                entry.sources.push(doc_info.url.clone());
+                entry.doc_sizes.push(doc_info.word_count);
+                entry.counts.push(*count);
+            }
+        }
+        if let Some(association) = results.associations.get(expr) {
+            entry.association_total.pmi += association.pmi;
+            entry.association_total.log_likelihood += association.log_likelihood;
+            entry.association_total.t_score += association.t_score;
+            entry.association_docs += 1;
+        }
+        if let Some(score) = results.scores.get(expr) {
+            entry.score_total += score;
+            entry.score_docs += 1;
+        }
+        if let Some(zones) = results.zones.get(expr) {
+            for (zone, occurrences) in zones {
+                *entry.zone_counts.entry(*zone).or_insert(0) += occurrences;
+            }
+        }
+        if let Some(forms) = results.surface_forms.and_then(|forms| forms.get(expr)) {
+            for (form, occurrences) in forms {
+                *entry.surface_forms.entry(form.clone()).or_insert(0) += occurrences;
+            }
         }
     }
 }
 
-fn collect_document_stats(analyzer: &mut TextAnalyzer, url: &str, doc_stats: &mut Vec<DocumentStats>) {
+fn collect_document_stats(
+    analyzer: &mut TextAnalyzer,
+    document: &StoredDocument,
+    doc_stats: &mut Vec<DocumentStats>,
+) {
+    let language = document.language;
     let (total_retained, total_unique, word_count) = analyzer.get_total_stats();
+    let sentence_count = analyzer.sentences().len();
+    let readability = analyzer.readability(language);
+    let entity_counts = analyzer.entity_counts();
+    let entity_count = |kind| entity_counts.get(&kind).copied().unwrap_or(0);
+    let entities = EntityStats {
+        numbers: entity_count(EntityKind::Number),
+        prices: entity_count(EntityKind::Price),
+        dates: entity_count(EntityKind::Date),
+        percentages: entity_count(EntityKind::Percentage),
+        measurements: entity_count(EntityKind::Measurement),
+    };
     analyzer.filter_banned_words();
     let avg_word_length = analyzer.average_word_length();
     
     doc_stats.push(DocumentStats {
-        url: url.to_string(),
+        url: document.url.clone(),
+        language,
         total_retained,
         total_unique,
         word_count,
+        sentence_count,
         average_word_length: avg_word_length,
+        readability: ReadabilityStats {
+            formula: reading_ease_formula(language).to_string(),
+            reading_ease: readability.reading_ease,
+            grade_level: readability.grade_level,
+            words_per_sentence: readability.words_per_sentence,
+            syllables_per_word: readability.syllables_per_word,
+        },
+        entities,
+        outline: document.outline.clone(),
+        metadata: document.metadata.clone(),
+        links: document.links.clone(),
+        link_stats: LinkStats::from_links(&document.links),
     });
 }
 
+// Types schema.org déclarés par les pages, du plus répandu au plus rare
+fn aggregate_schema_types(documents: &[StoredDocument], successful_urls: usize) -> Vec<SchemaTypeUsage> {
+    let mut sources: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for document in documents {
+        for schema_type in &document.metadata.schema_types {
+            sources.entry(schema_type).or_default().push(document.url.clone());
+        }
+    }
+
+    let mut usages: Vec<SchemaTypeUsage> = sources
+        .into_iter()
+        .map(|(schema_type, sources)| SchemaTypeUsage {
+            schema_type: schema_type.to_string(),
+            doc_count_percentage: sources.len() as f64 * 100.0 / successful_urls as f64,
+            sources,
+        })
+        .collect();
+    usages.sort_by(|a, b| b.doc_count_percentage.total_cmp(&a.doc_count_percentage));
+    usages
+}
+
+// Cumul des titres d'un même thème sur l'ensemble des documents
+#[derive(Default)]
+struct AggregatedHeadingTopic {
+    forms: HashMap<String, usize>,
+    levels: Vec<u8>,
+    occurrences: usize,
+    sources: Vec<String>,
+}
+
+// Termes protégés sous la forme des expressions comptées (découpés, en minuscules,
+// racinisés ou sans accents selon les réglages), pour chaque langue des pages
+pub(crate) fn protected_expressions(
+    documents: &[StoredDocument],
+    options: &AnalysisOptions,
+    analyzers: &mut AnalyzerCache,
+) -> HashSet<String> {
+    let languages: HashSet<Language> = documents.iter().map(|document| document.language).collect();
+    let mut expressions = HashSet::new();
+    for language in languages {
+        for term in &options.protected_terms {
+            let Ok(mut analyzer) = analyzers.analyzer(term, language, options) else {
+                continue;
+            };
+            let keys: Vec<&str> = analyzer.tokens().iter().map(|token| token.text.as_str()).collect();
+            expressions.insert(keys.join(" "));
+        }
+    }
+    expressions
+}
+
+// Regroupe les titres de toutes les pages par thème : chaque titre passe par
+// le même traitement que le contenu, puis ses mots vides sont retirés
+fn aggregate_heading_topics(
+    documents: &[StoredDocument],
+    successful_urls: usize,
+    options: &AnalysisOptions,
+    analyzers: &mut AnalyzerCache,
+) -> Vec<HeadingTopic> {
+    let mut topics: HashMap<String, AggregatedHeadingTopic> = HashMap::new();
+    for document in documents {
+        for heading in document.outline.iter().flat_map(Heading::flatten) {
+            // Un titre qui ne peut être analysé est ignoré, sans faire échouer la requête
+            let Ok(mut analyzer) = analyzers.analyzer(&heading.text, document.language, options) else {
+                continue;
+            };
+            // En mode « expand », un titre au masculin et au féminin compte pour les deux thèmes
+            for words in analyzer.content_words() {
+                let entry = topics.entry(words.join(" ")).or_default();
+                *entry.forms.entry(heading.text.clone()).or_insert(0) += 1;
+                if !entry.levels.contains(&heading.level) {
+                    entry.levels.push(heading.level);
+                }
+                entry.occurrences += 1;
+                if !entry.sources.contains(&document.url) {
+                    entry.sources.push(document.url.clone());
+                }
+            }
+        }
+    }
+
+    let mut results: Vec<HeadingTopic> = topics
+        .into_iter()
+        .map(|(topic, mut aggregated)| {
+            aggregated.levels.sort_unstable();
+            let display_form = aggregated
+                .forms
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+                .map_or_else(|| topic.clone(), |(form, _)| form);
+            HeadingTopic {
+                topic,
+                display_form,
+                levels: aggregated.levels,
+                occurrences: aggregated.occurrences,
+                doc_count_percentage: aggregated.sources.len() as f64 * 100.0 / successful_urls as f64,
+                sources: aggregated.sources,
+            }
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.doc_count_percentage
+            .total_cmp(&a.doc_count_percentage)
+            .then(b.occurrences.cmp(&a.occurrences))
+            .then(a.topic.cmp(&b.topic))
+    });
+    results.truncate(MAX_HEADING_TOPICS);
+    results
+}
+
 fn create_url_status(url: &str, success: bool, error: Option<String>) -> UrlStatus {
     UrlStatus {
         url: url.to_string(),
@@ -209,38 +1051,114 @@ fn create_url_status(url: &str, success: bool, error: Option<String>) -> UrlStat
     }
 }
 
-fn process_frequencies(
-    frequencies: HashMap<(String, String), (f64, f64, Vec<String>, Vec<usize>)>,
+pub(crate) fn process_frequencies(
+    frequencies: FrequencyMap,
     successful_urls: usize,
+    options: &AnalysisOptions,
+    protected: &HashSet<String>,
 ) -> Vec<FrequencyResult> {
     let mut results: Vec<FrequencyResult> = frequencies
         .into_iter()
-        .map(|((expr, gram_type), (count, percentage, sources, doc_sizes))| {
-            let doc_count = sources.len() as f64;
-            let average_doc_size = if !doc_sizes.is_empty() {
-                doc_sizes.iter().sum::<usize>() as f64 / doc_sizes.len() as f64
+        .map(|((expr, gram_type), aggregated)| {
+            let doc_count = aggregated.sources.len() as f64;
+            let average_doc_size = if !aggregated.doc_sizes.is_empty() {
+                aggregated.doc_sizes.iter().sum::<usize>() as f64
+                    / aggregated.doc_sizes.len() as f64
             } else {
                 0.0
             };
 
+            let mut surface_forms: Vec<SurfaceForm> = aggregated
+                .surface_forms
+                .into_iter()
+                .map(|(form, occurrences)| SurfaceForm { form, occurrences })
+                .collect();
+            surface_forms.sort_by(|a, b| b.occurrences.cmp(&a.occurrences).then(a.form.cmp(&b.form)));
+            let display_form = surface_forms
+                .first()
+                .map_or_else(|| expr.clone(), |form| form.form.clone());
+            // Une seule forme identique à l'expression n'apporte rien
+            if surface_forms.len() == 1 && surface_forms[0].form == expr {
+                surface_forms.clear();
+            }
+
+            let tf_idf_by_source: Vec<SourceScore> = aggregated
+                .sources
+                .iter()
+                .zip(aggregated.counts.iter().zip(&aggregated.doc_sizes))
+                .map(|(url, (&count, &doc_size))| SourceScore {
+                    url: url.clone(),
+                    tf_idf: tf_idf(count, doc_size, aggregated.sources.len(), successful_urls),
+                })
+                .collect();
+            let max_tf_idf = tf_idf_by_source
+                .iter()
+                .map(|score| score.tf_idf)
+                .fold(0.0, f64::max);
+
+            let association_docs = aggregated.association_docs as f64;
+            let average_association = |total: f64| {
+                (aggregated.association_docs > 0).then(|| total / association_docs)
+            };
+
+            let zone_score = options.zone_weights.score(&aggregated.zone_counts) / doc_count;
+
             FrequencyResult {
                 expression: expr,
+                display_form,
                 gram_type,
-                average_occurrences: count / doc_count,
-                average_percentage: percentage / doc_count,
+                average_occurrences: aggregated.total_count / doc_count,
+                average_percentage: aggregated.total_percentage / doc_count,
                 doc_count_percentage: (doc_count / successful_urls as f64) * 100.0,
                 average_doc_size,
-                sources,
+                sources: aggregated.sources,
+                tf_idf: max_tf_idf,
+                tf_idf_by_source,
+                pmi: average_association(aggregated.association_total.pmi),
+                log_likelihood: average_association(aggregated.association_total.log_likelihood),
+                t_score: average_association(aggregated.association_total.t_score),
+                score: (aggregated.score_docs > 0)
+                    .then(|| aggregated.score_total / aggregated.score_docs as f64),
+                zone_occurrences: aggregated.zone_counts.into_iter().collect(),
+                zone_score,
+                surface_forms,
+                subsumed_by: None,
             }
         })
+        .filter(|result| {
+            // Les mots seuls n'ont pas de mesure d'association et ne sont pas filtrés
+            let above = |value: Option<f64>, threshold: Option<f64>| match (value, threshold) {
+                (Some(value), Some(threshold)) => value >= threshold,
+                _ => true,
+            };
+            above(result.pmi, options.min_pmi)
+                && above(result.log_likelihood, options.min_log_likelihood)
+                && above(result.t_score, options.min_t_score)
+        })
         .collect();
 
+    if let Some(threshold) = options.subsumption_threshold {
+        prune_subsumed(&mut results, threshold, options.subsumption_mode, protected);
+    }
+
     results.sort_by(|a, b| {
-        b.doc_count_percentage
-            .partial_cmp(&a.doc_count_percentage)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then(b.average_occurrences.partial_cmp(&a.average_occurrences)
-            .unwrap_or(std::cmp::Ordering::Equal))
+        let by_doc_count = b.doc_count_percentage.total_cmp(&a.doc_count_percentage);
+        let by_occurrences = b.average_occurrences.total_cmp(&a.average_occurrences);
+        // Les expressions sans mesure d'association ou sans score sont classées en dernier
+        let by_association = |value: fn(&FrequencyResult) -> Option<f64>| {
+            let key = |result| value(result).unwrap_or(f64::NEG_INFINITY);
+            key(b).total_cmp(&key(a)).then(by_doc_count)
+        };
+        match options.sort_by {
+            SortKey::DocCount => by_doc_count.then(by_occurrences),
+            SortKey::Occurrences => by_occurrences.then(by_doc_count),
+            SortKey::TfIdf => b.tf_idf.total_cmp(&a.tf_idf).then(by_doc_count),
+            SortKey::Pmi => by_association(|result| result.pmi),
+            SortKey::LogLikelihood => by_association(|result| result.log_likelihood),
+            SortKey::TScore => by_association(|result| result.t_score),
+            SortKey::Score => by_association(|result| result.score),
+            SortKey::ZoneScore => b.zone_score.total_cmp(&a.zone_score).then(by_doc_count),
+        }
     });
 
     // Limiter à 1000 résultats
@@ -249,9 +1167,76 @@ fn process_frequencies(
     results
 }
 
+// Retire ou marque les expressions expliquées par une expression plus longue,
+// en comparant les occurrences cumulées sur tous les documents. Les termes
+// protégés sont conservés tels quels
+fn prune_subsumed(
+    results: &mut Vec<FrequencyResult>,
+    threshold: f64,
+    mode: SubsumptionMode,
+    protected: &HashSet<String>,
+) {
+    let counts: Vec<(&str, f64)> = results
+        .iter()
+        .map(|result| {
+            let total = result.average_occurrences * result.sources.len() as f64;
+            (result.expression.as_str(), total)
+        })
+        .collect();
+    let mut subsumed = find_subsumed(&counts, threshold);
+    subsumed.retain(|&shorter, _| !protected.contains(&results[shorter].expression));
+
+    match mode {
+        SubsumptionMode::Drop => {
+            let mut index = 0;
+            results.retain(|_| {
+                index += 1;
+                !subsumed.contains_key(&(index - 1))
+            });
+        }
+        SubsumptionMode::Mark => {
+            let containers: Vec<(usize, String)> = subsumed
+                .iter()
+                .map(|(&shorter, &longer)| (shorter, results[longer].expression.clone()))
+                .collect();
+            for (shorter, longer) in containers {
+                results[shorter].subsumed_by = Some(longer);
+            }
+        }
+    }
+}
+
+// TF-IDF d'une expression dans un document : fréquence relative pondérée par
+// l'IDF lissé ln((1 + N) / (1 + df)) + 1, N étant le nombre de documents analysés
+pub(crate) fn tf_idf(count: usize, doc_size: usize, doc_frequency: usize, corpus_size: usize) -> f64 {
+    if doc_size == 0 {
+        return 0.0;
+    }
+    let tf = count as f64 / doc_size as f64;
+    let idf = ((1.0 + corpus_size as f64) / (1.0 + doc_frequency as f64)).ln() + 1.0;
+    tf * idf
+}
+
 fn serialize_f64_2_decimals<S>(x: &f64, s: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
 {
     s.serialize_f64((x * 100.0).round() / 100.0)
-}
\ No newline at end of file
+}
+
+fn serialize_f64_4_decimals<S>(x: &f64, s: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    s.serialize_f64((x * 10000.0).round() / 10000.0)
+}
+
+fn serialize_option_f64_2_decimals<S>(x: &Option<f64>, s: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match x {
+        Some(x) => serialize_f64_2_decimals(x, s),
+        None => s.serialize_none(),
+    }
+}
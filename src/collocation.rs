@@ -0,0 +1,67 @@
+/// Association measures of a multi-word expression.
+///
+/// The expression is compared to the hypothesis that its words occur
+/// independently: PMI and t-score use the product of the word probabilities,
+/// the log-likelihood ratio (Dunning) splits it into its first n - 1 words and
+/// its last word.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Association {
+    pub pmi: f64,
+    pub log_likelihood: f64,
+    pub t_score: f64,
+}
+
+impl Association {
+    /// Computes the measures of an n-gram seen `count` times among `total`
+    /// positions, given the counts of each of its words, of its prefix
+    /// (first n - 1 words) and of its last word.
+    pub fn compute(
+        count: usize,
+        word_counts: &[usize],
+        prefix_count: usize,
+        last_count: usize,
+        total: usize,
+    ) -> Self {
+        let observed = count as f64;
+        let total_f = total as f64;
+        let expected = word_counts
+            .iter()
+            .map(|&c| c as f64 / total_f)
+            .product::<f64>()
+            * total_f;
+
+        Association {
+            pmi: if expected > 0.0 { (observed / expected).log2() } else { 0.0 },
+            log_likelihood: log_likelihood(count, prefix_count, last_count, total),
+            t_score: if observed > 0.0 {
+                (observed - expected) / observed.sqrt()
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+// Rapport de vraisemblance G² sur la table de contingence 2x2 (préfixe, dernier mot)
+fn log_likelihood(count: usize, prefix_count: usize, last_count: usize, total: usize) -> f64 {
+    let k11 = count as f64;
+    let k12 = prefix_count.saturating_sub(count) as f64;
+    let k21 = last_count.saturating_sub(count) as f64;
+    let k22 = (total + count).saturating_sub(prefix_count + last_count) as f64;
+    let n = k11 + k12 + k21 + k22;
+    if n == 0.0 {
+        return 0.0;
+    }
+
+    let cells = [
+        (k11, k11 + k12, k11 + k21),
+        (k12, k11 + k12, k12 + k22),
+        (k21, k21 + k22, k11 + k21),
+        (k22, k21 + k22, k12 + k22),
+    ];
+    2.0 * cells
+        .iter()
+        .filter(|(observed, _, _)| *observed > 0.0)
+        .map(|&(observed, row, column)| observed * (observed * n / (row * column)).ln())
+        .sum::<f64>()
+}
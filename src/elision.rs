@@ -0,0 +1,170 @@
+use std::error::Error;
+use std::fs;
+
+// Apostrophes reconnues dans les règles, avant ou sans `normalize_apostrophes`
+const APOSTROPHES: [char; 5] = ['\'', '\u{2019}', '\u{2018}', '\u{201B}', '\u{02BC}'];
+
+/// Elision and contraction rules of a language.
+///
+/// Rules are read from a data file with one rule per line:
+///
+/// - `prefix l'` removes an elided article or pronoun ("l'école" gives "école"),
+/// - `suffix n't not` replaces a contracted ending ("don't" gives "do not"),
+///   the replacement being optional ("company's" gives "company"),
+/// - `word won't will not` replaces a whole word.
+///
+/// Matching ignores case and accepts typographic apostrophes, so "L’École"
+/// gives "École". Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct ElisionRules {
+    prefixes: Vec<Rule>,
+    suffixes: Vec<Rule>,
+    words: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: String,
+    replacement: Option<String>,
+}
+
+impl ElisionRules {
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let mut rules = ElisionRules::default();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let kind = parts.next().unwrap_or_default();
+            let Some(pattern) = parts.next() else {
+                return Err(format!("Règle d'élision sans motif à la ligne {}", number + 1).into());
+            };
+            let replacement: Vec<&str> = parts.collect();
+            let rule = Rule {
+                pattern: pattern.to_lowercase(),
+                replacement: (!replacement.is_empty()).then(|| replacement.join(" ")),
+            };
+            match kind {
+                "prefix" => rules.prefixes.push(rule),
+                "suffix" => rules.suffixes.push(rule),
+                "word" => rules.words.push(rule),
+                _ => {
+                    return Err(format!(
+                        "Type de règle d'élision inconnu à la ligne {} : {}",
+                        number + 1,
+                        kind
+                    )
+                    .into())
+                }
+            }
+        }
+
+        // Les motifs les plus longs sont essayés en premier (« jusqu' » avant « qu' »)
+        for list in [&mut rules.prefixes, &mut rules.suffixes] {
+            list.sort_by_key(|rule| std::cmp::Reverse(rule.pattern.chars().count()));
+        }
+        Ok(rules)
+    }
+
+    /// Applies the rules to a single word, without surrounding punctuation.
+    ///
+    /// Returns the word unchanged when no rule matches.
+    pub fn apply(&self, word: &str) -> String {
+        if let Some(rule) = self
+            .words
+            .iter()
+            .find(|rule| matches_at(word, &rule.pattern) == Some(word.len()))
+        {
+            return rule.replacement.clone().unwrap_or_default();
+        }
+
+        let mut word = word;
+        let mut prefix_replacement = None;
+        for rule in &self.prefixes {
+            if let Some(end) = matches_at(word, &rule.pattern) {
+                // Une élision seule (« l' ») reste telle quelle
+                if end < word.len() {
+                    prefix_replacement = rule.replacement.as_deref();
+                    word = &word[end..];
+                }
+                break;
+            }
+        }
+
+        let mut result = String::new();
+        if let Some(replacement) = prefix_replacement {
+            result.push_str(replacement);
+            result.push(' ');
+        }
+        match self.suffixes.iter().find_map(|rule| {
+            suffix_start(word, &rule.pattern)
+                .filter(|&start| start > 0)
+                .map(|start| (start, rule))
+        }) {
+            Some((start, rule)) => {
+                result.push_str(&word[..start]);
+                if let Some(replacement) = &rule.replacement {
+                    result.push(' ');
+                    result.push_str(replacement);
+                }
+            }
+            None => result.push_str(word),
+        }
+        result
+    }
+}
+
+/// Path of the elision rules shipped next to a stop-word list
+/// ("stop_words_french.txt" gives "elisions_french.txt").
+pub fn elisions_path(stop_words_path: &str) -> String {
+    match stop_words_path.rfind("stop_words") {
+        Some(index) => format!(
+            "{}elisions{}",
+            &stop_words_path[..index],
+            &stop_words_path[index + "stop_words".len()..]
+        ),
+        None => stop_words_path.to_string(),
+    }
+}
+
+// Compare deux caractères sans tenir compte de la casse ni du type d'apostrophe
+fn same_char(a: char, b: char) -> bool {
+    if APOSTROPHES.contains(&a) && APOSTROPHES.contains(&b) {
+        return true;
+    }
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+// Position de fin du motif s'il commence le mot
+fn matches_at(word: &str, pattern: &str) -> Option<usize> {
+    let mut chars = word.char_indices();
+    for expected in pattern.chars() {
+        let (_, c) = chars.next()?;
+        if !same_char(c, expected) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(word.len(), |(index, _)| index))
+}
+
+// Position de début du motif s'il termine le mot
+fn suffix_start(word: &str, pattern: &str) -> Option<usize> {
+    let mut chars = word.char_indices().rev();
+    let mut start = word.len();
+    for expected in pattern.chars().rev() {
+        let (index, c) = chars.next()?;
+        if !same_char(c, expected) {
+            return None;
+        }
+        start = index;
+    }
+    Some(start)
+}
@@ -0,0 +1,352 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::tokenizer::Token;
+
+// Mois français et anglais, avec leurs abréviations courantes
+const MONTHS: &[&str] = &[
+    "janvier", "février", "fevrier", "mars", "avril", "mai", "juin", "juillet", "août", "aout",
+    "septembre", "octobre", "novembre", "décembre", "decembre", "janv", "févr", "fevr", "avr",
+    "juil", "sept", "oct", "nov", "déc", "dec", "january", "february", "march", "april", "may",
+    "june", "july", "august", "september", "october", "november", "december", "jan", "feb",
+    "mar", "apr", "jun", "jul", "aug", "sep",
+];
+
+// Mois qui sont aussi des mots courants (« you may », « ils étaient sept ») : sans
+// majuscule, ils ne comptent comme mois que dans une date complète
+const AMBIGUOUS_MONTHS: &[&str] = &["may", "mar", "sept"];
+
+// Devises écrites en toutes lettres après un montant
+const CURRENCY_WORDS: &[&str] = &[
+    "euro", "euros", "eur", "dollar", "dollars", "usd", "gbp", "pounds", "chf",
+];
+
+const CURRENCY_SYMBOLS: &[char] = &['€', '$', '£', '¥'];
+
+// Espaces qui peuvent séparer un nombre de son symbole
+const SPACES: [char; 4] = [' ', '\t', '\u{A0}', '\u{202F}'];
+
+// Unités de mesure reconnues après un nombre (« 5 km », « 16go », « 20 °C »)
+const UNITS: &[&str] = &[
+    "mm", "cm", "m", "km", "m²", "m2", "km²", "m³", "m3", "ha", "mg", "g", "kg", "ml", "cl", "dl",
+    "l", "ms", "min", "h", "jours", "km/h", "mph", "ft", "lb", "lbs", "oz", "w", "kw", "kwh",
+    "mw", "v", "hz", "khz", "mhz", "ghz", "ko", "mo", "go", "kb", "mb", "gb", "tb", "px", "dpi",
+    "°", "°c", "°f",
+];
+
+/// Kind of a typed entity detected among the tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Number,
+    Price,
+    Date,
+    Percentage,
+    Measurement,
+}
+
+impl EntityKind {
+    /// Placeholder counted instead of the entity ("<PRICE>" for "99,99 €").
+    pub fn placeholder(self) -> &'static str {
+        match self {
+            EntityKind::Number => "<NUMBER>",
+            EntityKind::Price => "<PRICE>",
+            EntityKind::Date => "<DATE>",
+            EntityKind::Percentage => "<PERCENT>",
+            EntityKind::Measurement => "<MEASURE>",
+        }
+    }
+}
+
+/// What to do with the typed entities when counting expressions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityMode {
+    /// Entities are counted as ordinary tokens.
+    #[default]
+    Keep,
+    /// Each entity becomes a single typed placeholder token.
+    Placeholder,
+    /// Entities are left out of the n-grams and keyphrases.
+    Exclude,
+}
+
+/// Typed entity spanning a range of tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub kind: EntityKind,
+    pub tokens: Range<usize>,
+    // Positions d'octets dans le texte, symboles (« € », « % ») compris
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Detects numbers, currency amounts, dates, percentages and measurements.
+///
+/// Detection works on the tokens and on the characters around them, so that
+/// symbols dropped by the tokenizer are taken into account: "99,99 €" is a
+/// price, "100%" a percentage, "12/03/2024" and "15 janvier 2024" are dates,
+/// and "5 km" or "16Go" are measurements. Other numeric tokens are numbers.
+pub fn detect_entities(text: &str, tokens: &[Token]) -> Vec<Entity> {
+    let mut entities = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let word = tokens[i].text.as_str();
+        let entity = if is_month(word) {
+            month_first_date(tokens, i)
+        } else if is_number(word) || is_day(word) {
+            numeric_date(text, tokens, i)
+                .or_else(|| day_first_date(tokens, i))
+                .or_else(|| number_entity(text, tokens, i))
+        } else if is_attached_measurement(word) {
+            Some(Entity::single(EntityKind::Measurement, tokens, i))
+        } else {
+            None
+        };
+
+        match entity {
+            Some(entity) => {
+                i = entity.tokens.end;
+                entities.push(entity);
+            }
+            None => i += 1,
+        }
+    }
+    entities
+}
+
+impl Entity {
+    fn single(kind: EntityKind, tokens: &[Token], i: usize) -> Self {
+        Entity::spanning(kind, tokens, i, i)
+    }
+
+    // Entité couvrant les tokens `first..=last`
+    fn spanning(kind: EntityKind, tokens: &[Token], first: usize, last: usize) -> Self {
+        Entity {
+            kind,
+            tokens: first..last + 1,
+            start: tokens[first].start,
+            end: tokens[last].end,
+        }
+    }
+}
+
+// Montant, pourcentage, mesure ou simple nombre commençant au token `i`
+fn number_entity(text: &str, tokens: &[Token], i: usize) -> Option<Entity> {
+    let token = &tokens[i];
+    if !is_number(&token.text) {
+        return None;
+    }
+
+    let after = after_token(text, token.end);
+    let before = text[..token.start].trim_end_matches(SPACES);
+    if let Some(symbol) = after.chars().next().filter(|&c| c == '%' || c == '°') {
+        let end = symbol_end(text, token.end, symbol);
+        let kind = if symbol == '%' {
+            EntityKind::Percentage
+        } else {
+            EntityKind::Measurement
+        };
+        // « 20 °C » : l'unité qui suit le degré fait partie de la mesure
+        let unit = tokens.get(i + 1).filter(|unit| {
+            symbol == '°'
+                && unit.start == end
+                && UNITS.contains(&format!("°{}", unit.text.to_lowercase()).as_str())
+        });
+        return Some(Entity {
+            kind,
+            tokens: i..i + 1 + usize::from(unit.is_some()),
+            start: token.start,
+            end: unit.map_or(end, |unit| unit.end),
+        });
+    }
+    if let Some(symbol) = after
+        .chars()
+        .next()
+        .filter(|c| CURRENCY_SYMBOLS.contains(c))
+    {
+        return Some(Entity {
+            kind: EntityKind::Price,
+            tokens: i..i + 1,
+            start: token.start,
+            end: symbol_end(text, token.end, symbol),
+        });
+    }
+    if let Some(symbol) = before
+        .chars()
+        .next_back()
+        .filter(|c| CURRENCY_SYMBOLS.contains(c))
+    {
+        return Some(Entity {
+            kind: EntityKind::Price,
+            tokens: i..i + 1,
+            start: before.len() - symbol.len_utf8(),
+            end: token.end,
+        });
+    }
+
+    // Devise, pourcentage ou unité écrits dans le mot suivant
+    let next = tokens
+        .get(i + 1)
+        .filter(|next| text[token.end..next.start].trim_matches(SPACES).is_empty())
+        .map(|next| next.text.to_lowercase());
+    let kind = match next.as_deref() {
+        Some(word) if CURRENCY_WORDS.contains(&word) => Some(EntityKind::Price),
+        Some("percent" | "pourcent") => Some(EntityKind::Percentage),
+        Some(word) if UNITS.contains(&word) => Some(EntityKind::Measurement),
+        _ => None,
+    };
+    // « livres » désigne le plus souvent des ouvrages : seules les livres sterling sont un montant
+    let sterling = matches!(next.as_deref(), Some("livre" | "livres"))
+        && tokens
+            .get(i + 2)
+            .is_some_and(|currency| currency.text.to_lowercase() == "sterling");
+    if sterling {
+        return Some(Entity::spanning(EntityKind::Price, tokens, i, i + 2));
+    }
+    Some(match kind {
+        Some(kind) => Entity::spanning(kind, tokens, i, i + 1),
+        None => Entity::single(EntityKind::Number, tokens, i),
+    })
+}
+
+// Texte qui suit un token, espaces (insécables comprises) ignorées
+fn after_token(text: &str, end: usize) -> &str {
+    text[end..].trim_start_matches(SPACES)
+}
+
+// Fin du symbole qui suit un nombre
+fn symbol_end(text: &str, end: usize, symbol: char) -> usize {
+    let offset = text[end..].find(symbol).unwrap_or(0);
+    end + offset + symbol.len_utf8()
+}
+
+/// Whether a word is a number written with digits ("2024", "99,99", "1'000").
+pub(crate) fn is_number(word: &str) -> bool {
+    word.chars().any(|c| c.is_ascii_digit())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '\''))
+}
+
+fn is_month(word: &str) -> bool {
+    MONTHS.contains(&word.to_lowercase().as_str())
+}
+
+fn is_ambiguous_month(word: &str) -> bool {
+    AMBIGUOUS_MONTHS.contains(&word)
+}
+
+fn is_year(word: &str) -> bool {
+    word.len() == 4 && word.chars().all(|c| c.is_ascii_digit())
+}
+
+// Jour du mois, éventuellement ordinal (« 1er », « 2nd », « 15th »)
+fn is_day(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    let digits = ["er", "st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| lower.strip_suffix(suffix))
+        .unwrap_or(&lower);
+    digits
+        .parse::<u32>()
+        .is_ok_and(|day| (1..=31).contains(&day))
+}
+
+// Nombre suivi d'une unité collée (« 5km », « 16go »)
+fn is_attached_measurement(word: &str) -> bool {
+    let split = word
+        .find(|c: char| !(c.is_ascii_digit() || c == ',' || c == '.'))
+        .unwrap_or(word.len());
+    split > 0
+        && word[..split].chars().any(|c| c.is_ascii_digit())
+        && UNITS.contains(&word[split..].to_lowercase().as_str())
+}
+
+// « 15 janvier 2024 », « 1er mai »
+fn day_first_date(tokens: &[Token], i: usize) -> Option<Entity> {
+    if !is_day(&tokens[i].text) || !tokens.get(i + 1).is_some_and(|month| is_month(&month.text)) {
+        return None;
+    }
+    let last = if tokens.get(i + 2).is_some_and(|year| is_year(&year.text)) {
+        i + 2
+    } else if is_ambiguous_month(&tokens[i + 1].text) {
+        return None;
+    } else {
+        i + 1
+    };
+    Some(Entity::spanning(EntityKind::Date, tokens, i, last))
+}
+
+// « janvier 2024 », « March 15, 2024 »
+fn month_first_date(tokens: &[Token], i: usize) -> Option<Entity> {
+    let next = tokens.get(i + 1)?;
+    let complete = is_day(&next.text) && tokens.get(i + 2).is_some_and(|year| is_year(&year.text));
+    if !complete && is_ambiguous_month(&tokens[i].text) {
+        return None;
+    }
+    let last = if is_year(&next.text) {
+        i + 1
+    } else if is_day(&next.text) {
+        if tokens.get(i + 2).is_some_and(|year| is_year(&year.text)) {
+            i + 2
+        } else {
+            i + 1
+        }
+    } else {
+        return None;
+    };
+    Some(Entity::spanning(EntityKind::Date, tokens, i, last))
+}
+
+// « 12/03/2024 », « 12.03.24 », « 2024-03-12 »
+fn numeric_date(text: &str, tokens: &[Token], i: usize) -> Option<Entity> {
+    let start = tokens[i].start;
+    let chunk_end = text[start..]
+        .find(char::is_whitespace)
+        .map_or(text.len(), |offset| start + offset);
+    let chunk = text[start..chunk_end].trim_end_matches(|c: char| !c.is_ascii_digit());
+    let parts: Vec<&str> = chunk.split(['/', '.', '-']).collect();
+    let separators: Vec<char> = chunk
+        .chars()
+        .filter(|c| matches!(c, '/' | '.' | '-'))
+        .collect();
+    if parts.len() != 3
+        || separators.iter().any(|&c| c != separators[0])
+        || parts
+            .iter()
+            .any(|part| part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+
+    // Une partie trop grande pour un u32 n'est pas une date
+    let numbers = parts
+        .iter()
+        .map(|part| part.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    if numbers.len() != 3 {
+        return None;
+    }
+    let valid = if parts[0].len() == 4 {
+        (1..=12).contains(&numbers[1]) && (1..=31).contains(&numbers[2])
+    } else {
+        (1..=31).contains(&numbers[0])
+            && (1..=12).contains(&numbers[1])
+            && matches!(parts[2].len(), 2 | 4)
+    };
+    if !valid {
+        return None;
+    }
+
+    let end = start + chunk.len();
+    let last = (i..tokens.len())
+        .take_while(|&j| tokens[j].start < end)
+        .last()?;
+    Some(Entity {
+        kind: EntityKind::Date,
+        tokens: i..last + 1,
+        start,
+        end: end.max(tokens[last].end),
+    })
+}
@@ -0,0 +1,200 @@
+use serde::Deserialize;
+
+// Points médians et séparateurs équivalents (« professeur·e », « élu•e·s »)
+const MIDDLE_DOTS: [char; 4] = ['·', '•', '‧', '⋅'];
+
+// Suffixes féminins reconnus après le séparateur
+const FEMININE_SUFFIXES: &[&str] = &[
+    "e", "ne", "le", "se", "ve", "te", "fe", "euse", "rice", "trice", "ice", "esse",
+];
+
+/// How gender-inclusive French forms are counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InclusiveMode {
+    /// Every spelling is kept as one token, written with middle dots:
+    /// "enseignant.e.s", "enseignant-e-s" and "enseignant(e)s" all give
+    /// "enseignant·e·s".
+    #[default]
+    Keep,
+    /// The form is counted as its masculine and feminine words:
+    /// "enseignant·e·s" gives "enseignants" and "enseignantes".
+    Expand,
+    /// Inclusive forms are left to the tokenizer.
+    Off,
+}
+
+/// Gender-inclusive word, such as "acteur·rice·s".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusiveForm {
+    base: String,
+    feminine: String,
+    plural: bool,
+}
+
+impl InclusiveForm {
+    /// Recognizes an inclusive word written with middle dots ("professeur·e",
+    /// "élu·es"), periods ("enseignant.e.s"), hyphens ("étudiant-e-s") or
+    /// parentheses ("étudiant(e)s").
+    pub fn parse(word: &str) -> Option<Self> {
+        let split = word.find(|c: char| !c.is_alphabetic())?;
+        let (base, rest) = word.split_at(split);
+        if base.chars().count() < 3 {
+            return None;
+        }
+        let separator = rest.chars().next()?;
+        let rest = &rest[separator.len_utf8()..];
+
+        let (feminine, plural) = if MIDDLE_DOTS.contains(&separator) {
+            // « ·e », « ·e·s » ou « ·es »
+            match rest.split_once(MIDDLE_DOTS) {
+                Some((feminine, "s")) => (feminine, true),
+                Some(_) => return None,
+                None if !is_feminine(rest) && rest.len() > 1 && rest.ends_with('s') => {
+                    (&rest[..rest.len() - 1], true)
+                }
+                None => (rest, false),
+            }
+        } else if separator == '.' || separator == '-' {
+            // Le pluriel est toujours séparé (« .e.s »), pour ne pas confondre
+            // « site.es » ou « porte-monnaie » avec une forme inclusive
+            match rest.split_once(separator) {
+                Some((feminine, "s")) => (feminine, true),
+                Some(_) => return None,
+                None => (rest, false),
+            }
+        } else if separator == '(' {
+            let (feminine, after) = rest.split_once(')')?;
+            match after {
+                "" => (feminine, false),
+                "s" => (feminine, true),
+                _ => return None,
+            }
+        } else {
+            return None;
+        };
+
+        // Après un trait d'union ou un point, le suffixe peut être un pronom
+        // (« prends-le ») ou le début d'une phrase collée (« la fin.Le début ») :
+        // il doit alors être en minuscules et prolonger la fin du mot
+        let guarded = separator == '-' || separator == '.';
+        if guarded && (!is_lowercase(feminine) || !inflects(base, feminine)) {
+            return None;
+        }
+
+        is_feminine(feminine).then(|| InclusiveForm {
+            base: base.to_string(),
+            feminine: feminine.to_string(),
+            plural,
+        })
+    }
+
+    /// Single spelling shared by all the variants.
+    pub fn canonical(&self) -> String {
+        let mut canonical = format!("{}·{}", self.base, self.feminine);
+        if self.plural {
+            canonical.push_str("·s");
+        }
+        canonical
+    }
+
+    pub fn masculine(&self) -> String {
+        if self.plural && !self.base.ends_with(['s', 'x']) {
+            format!("{}s", self.base)
+        } else {
+            self.base.clone()
+        }
+    }
+
+    pub fn feminine(&self) -> String {
+        let base = self.base.as_str();
+        let lower_feminine = self.feminine.to_lowercase();
+        let stem = if lower_feminine != "e" && base.to_lowercase().ends_with("eur") {
+            // « acteur·rice » → « actrice », « danseur·euse » → « danseuse »
+            let stem = &base[..base.len() - "eur".len()];
+            match (stem.chars().last(), lower_feminine.chars().next()) {
+                (Some(last), Some(first)) if last.to_lowercase().eq(first.to_lowercase()) => {
+                    &stem[..stem.len() - last.len_utf8()]
+                }
+                _ => stem,
+            }
+        } else if (lower_feminine == "ve" && base.ends_with('f'))
+            || (lower_feminine == "se" && base.ends_with('x'))
+        {
+            // « sportif·ve » → « sportive », « heureux·se » → « heureuse »
+            &base[..base.len() - 1]
+        } else {
+            base
+        };
+
+        let mut feminine = format!("{}{}", stem, self.feminine);
+        if self.plural {
+            feminine.push('s');
+        }
+        feminine
+    }
+}
+
+/// Rewrites every inclusive word of a text with its canonical spelling.
+///
+/// Punctuation around words is kept ("(enseignant.e.s)" gives "(enseignant·e·s)").
+pub fn canonicalize_inclusive(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, chunk) in text
+        .split(char::is_whitespace)
+        .filter(|chunk| !chunk.is_empty())
+        .map(|chunk| (chunk.as_ptr() as usize - text.as_ptr() as usize, chunk))
+    {
+        result.push_str(&text[last..start]);
+        last = start + chunk.len();
+
+        let Some(first) = chunk.find(char::is_alphanumeric) else {
+            result.push_str(chunk);
+            continue;
+        };
+        // La parenthèse fermante fait partie de « étudiant(e) »
+        let mut end = chunk
+            .rfind(char::is_alphanumeric)
+            .map_or(chunk.len(), |index| {
+                index + chunk[index..].chars().next().map_or(0, char::len_utf8)
+            });
+        if chunk[first..end].contains('(') && chunk[end..].starts_with(')') {
+            end += 1;
+        }
+
+        match InclusiveForm::parse(&chunk[first..end]) {
+            Some(form) => {
+                result.push_str(&chunk[..first]);
+                result.push_str(&form.canonical());
+                result.push_str(&chunk[end..]);
+            }
+            None => result.push_str(chunk),
+        }
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+// Vrai si le suffixe féminin correspond à la terminaison du mot
+// (« citoyen-ne », « professionnel-le », « acteur-rice », « sportif-ve »)
+fn inflects(base: &str, suffix: &str) -> bool {
+    let base = base.to_lowercase();
+    match suffix.to_lowercase().as_str() {
+        "ne" => base.ends_with('n'),
+        "le" => base.ends_with('l'),
+        "te" => base.ends_with('t'),
+        "se" => base.ends_with(['x', 's']),
+        "ve" | "fe" => base.ends_with('f'),
+        "euse" | "rice" | "trice" | "ice" => base.ends_with("eur"),
+        _ => true,
+    }
+}
+
+fn is_lowercase(suffix: &str) -> bool {
+    suffix.chars().all(char::is_lowercase)
+}
+
+fn is_feminine(suffix: &str) -> bool {
+    FEMININE_SUFFIXES.contains(&suffix.to_lowercase().as_str())
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use whatlang::{Detector, Lang};
+
+/// Languages supported by the analyzer (stop words, stemming...).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "en")]
+    English,
+}
+
+impl Language {
+    pub fn stop_words_path(self) -> &'static str {
+        match self {
+            Language::French => "stop_words_french.txt",
+            Language::English => "stop_words_english.txt",
+        }
+    }
+
+    pub fn pos_lexicon_path(self) -> &'static str {
+        match self {
+            Language::French => "pos_lexicon_french.txt",
+            Language::English => "pos_lexicon_english.txt",
+        }
+    }
+
+    /// Parses a language tag such as `fr`, `fr-FR` or `en_US`.
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        let primary = tag.split(['-', '_']).next()?.trim().to_lowercase();
+        match primary.as_str() {
+            "fr" | "fra" | "fre" => Some(Language::French),
+            "en" | "eng" => Some(Language::English),
+            _ => None,
+        }
+    }
+}
+
+// Nombre de caractères suffisant pour identifier la langue d'une page
+const DETECTION_SAMPLE_CHARS: usize = 20_000;
+
+/// Detects the language of a document with an offline trigram identifier.
+///
+/// The `hint` (usually the `<html lang>` attribute) is used when the detection
+/// is not reliable, and French is assumed when neither gives an answer.
+pub fn detect_language(text: &str, hint: Option<&str>) -> Language {
+    let hinted = hint.and_then(Language::from_tag);
+    let sample: String = text.chars().take(DETECTION_SAMPLE_CHARS).collect();
+    let detected = Detector::with_allowlist(vec![Lang::Fra, Lang::Eng]).detect(&sample);
+
+    match detected {
+        Some(info) if info.is_reliable() || hinted.is_none() => match info.lang() {
+            Lang::Eng => Language::English,
+            _ => Language::French,
+        },
+        _ => hinted.unwrap_or_default(),
+    }
+}
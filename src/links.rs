@@ -0,0 +1,115 @@
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::Serialize;
+
+/// Whether a link stays on the analyzed site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    Internal,
+    External,
+}
+
+/// Link of a page, resolved to an absolute URL.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Link {
+    pub url: String,
+    pub anchor_text: String,
+    // Valeurs de l'attribut rel, en minuscules (« nofollow », « sponsored », « ugc »)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rel: Vec<String>,
+    pub kind: LinkKind,
+}
+
+impl Link {
+    pub fn has_rel(&self, value: &str) -> bool {
+        self.rel.iter().any(|rel| rel == value)
+    }
+}
+
+/// Link counts of a page.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LinkStats {
+    pub total: usize,
+    pub internal: usize,
+    pub external: usize,
+    pub nofollow: usize,
+    pub sponsored: usize,
+    pub ugc: usize,
+}
+
+impl LinkStats {
+    pub fn from_links(links: &[Link]) -> Self {
+        let count = |rel: &str| links.iter().filter(|link| link.has_rel(rel)).count();
+        let internal = links.iter().filter(|link| link.kind == LinkKind::Internal).count();
+        LinkStats {
+            total: links.len(),
+            internal,
+            external: links.len() - internal,
+            nofollow: count("nofollow"),
+            sponsored: count("sponsored"),
+            ugc: count("ugc"),
+        }
+    }
+}
+
+/// Lists the `<a href>` elements of the page. Relative URLs are resolved
+/// against `<base href>` or the page URL; links that do not lead to an
+/// http(s) page (`mailto:`, `javascript:`…) are skipped.
+pub fn extract_links(document: &Html, page_url: &str) -> Vec<Link> {
+    let Ok(page_url) = Url::parse(page_url) else {
+        return Vec::new();
+    };
+    let base_selector = Selector::parse("base[href]").unwrap();
+    let base_url = document
+        .select(&base_selector)
+        .next()
+        .and_then(|base| page_url.join(base.value().attr("href")?).ok())
+        .unwrap_or_else(|| page_url.clone());
+
+    let link_selector = Selector::parse("a[href]").unwrap();
+    let image_selector = Selector::parse("img[alt]").unwrap();
+    document
+        .select(&link_selector)
+        .filter_map(|link| {
+            let href = link.value().attr("href")?.trim();
+            let url = base_url.join(href).ok()?;
+            if url.scheme() != "http" && url.scheme() != "https" {
+                return None;
+            }
+
+            // Un lien sans texte est décrit par le texte alternatif de son image, ou par ses attributs
+            let mut anchor_text = link.text().collect::<Vec<_>>().join(" ");
+            if anchor_text.trim().is_empty() {
+                anchor_text = link
+                    .select(&image_selector)
+                    .filter_map(|image| image.value().attr("alt"))
+                    .chain(link.value().attr("aria-label"))
+                    .chain(link.value().attr("title"))
+                    .find(|text| !text.trim().is_empty())
+                    .unwrap_or_default()
+                    .to_string();
+            }
+
+            Some(Link {
+                kind: if same_site(&url, &page_url) { LinkKind::Internal } else { LinkKind::External },
+                url: url.to_string(),
+                anchor_text: anchor_text.split_whitespace().collect::<Vec<_>>().join(" "),
+                rel: link
+                    .value()
+                    .attr("rel")
+                    .map(|rel| rel.split_whitespace().map(str::to_lowercase).collect())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+// Même hôte, au préfixe « www. » près
+fn same_site(url: &Url, page_url: &Url) -> bool {
+    let host = |url: &Url| {
+        url.host_str()
+            .map(|host| host.trim_start_matches("www.").to_lowercase())
+    };
+    host(url).is_some() && host(url) == host(page_url)
+}
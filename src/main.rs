@@ -1,8 +1,24 @@
+#[allow(clippy::module_inception)]
 mod tests;
+mod collocation;
+mod elision;
+mod entity;
+mod inclusive;
+mod language;
+mod links;
+mod metadata;
+mod normalizer;
+mod pos;
+mod readability;
+mod sentence;
+mod stemmer;
+mod subsumption;
 mod text_analyzer;
+mod tokenizer;
 mod web_analyzer;
+mod zone;
 mod api;
-use actix_web::{App, HttpServer};
+use actix_web::{web, App, HttpServer};
 use actix_web::middleware::Logger;
 
 #[actix_web::main]
@@ -12,10 +28,15 @@ async fn main() -> std::io::Result<()> {
     
     println!("Serveur démarré sur http://{}:{}", host, port);
 
-    HttpServer::new(|| {
+    // Analyses conservées pour le concordancier, partagées entre les workers
+    let store = web::Data::new(api::AnalysisStore::default());
+
+    HttpServer::new(move || {
         App::new()
             .wrap(Logger::default())
+            .app_data(store.clone())
             .service(api::analyze_urls)
+            .service(api::concordance)
     })
     .bind((host, port.parse::<u16>().unwrap()))?
     .run()
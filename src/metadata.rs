@@ -0,0 +1,265 @@
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Metadata of a page: title, meta tags and structured data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PageMetadata {
+    pub title: String,
+    pub description: String,
+    pub keywords: String,
+    #[serde(skip_serializing_if = "OpenGraph::is_empty")]
+    pub open_graph: OpenGraph,
+    #[serde(skip_serializing_if = "TwitterCard::is_empty")]
+    pub twitter_card: TwitterCard,
+    // Blocs JSON-LD tels qu'ils sont déclarés
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub json_ld: Vec<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub microdata: Vec<MicrodataItem>,
+    // Types schema.org déclarés en JSON-LD ou en microdonnées (« FAQPage », « Course »)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub schema_types: Vec<String>,
+}
+
+/// OpenGraph tags (`og:*`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OpenGraph {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+impl OpenGraph {
+    pub fn is_empty(&self) -> bool {
+        *self == OpenGraph::default()
+    }
+}
+
+/// Twitter card tags (`twitter:*`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TwitterCard {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+}
+
+impl TwitterCard {
+    pub fn is_empty(&self) -> bool {
+        *self == TwitterCard::default()
+    }
+}
+
+/// Microdata item (`itemscope`), with its properties in document order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MicrodataItem {
+    #[serde(rename = "type", skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    pub properties: BTreeMap<String, Vec<MicrodataValue>>,
+}
+
+/// Value of a microdata property: text, or a nested item.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum MicrodataValue {
+    Text(String),
+    Item(MicrodataItem),
+}
+
+/// Reads the OpenGraph and Twitter card tags, whether declared with `property` or `name`.
+pub fn parse_social_tags(document: &Html) -> (OpenGraph, TwitterCard) {
+    let meta_selector = Selector::parse("meta[content]").unwrap();
+    let mut open_graph = OpenGraph::default();
+    let mut twitter_card = TwitterCard::default();
+
+    for meta in document.select(&meta_selector) {
+        let Some(key) = meta.value().attr("property").or(meta.value().attr("name")) else { continue };
+        let content = meta.value().attr("content").unwrap_or_default().trim().to_string();
+        if content.is_empty() {
+            continue;
+        }
+        let field = match key.trim().to_lowercase().as_str() {
+            "og:title" => &mut open_graph.title,
+            "og:description" => &mut open_graph.description,
+            "og:type" => &mut open_graph.kind,
+            "og:url" => &mut open_graph.url,
+            "og:image" | "og:image:url" => &mut open_graph.image,
+            "og:site_name" => &mut open_graph.site_name,
+            "og:locale" => &mut open_graph.locale,
+            "twitter:card" => &mut twitter_card.card,
+            "twitter:title" => &mut twitter_card.title,
+            "twitter:description" => &mut twitter_card.description,
+            "twitter:image" | "twitter:image:src" => &mut twitter_card.image,
+            "twitter:site" => &mut twitter_card.site,
+            "twitter:creator" => &mut twitter_card.creator,
+            _ => continue,
+        };
+        // La première déclaration l'emporte (plusieurs `og:image` sont courantes)
+        field.get_or_insert(content);
+    }
+    (open_graph, twitter_card)
+}
+
+/// Parses the `application/ld+json` blocks. Invalid blocks are skipped,
+/// and top-level arrays are flattened.
+pub fn parse_json_ld(document: &Html) -> Vec<Value> {
+    let script_selector = Selector::parse("script[type='application/ld+json']").unwrap();
+    let mut blocks = Vec::new();
+
+    for script in document.select(&script_selector) {
+        let text = script.text().collect::<String>();
+        // Certains CMS enveloppent le JSON dans un commentaire ou une section CDATA
+        let text = text
+            .trim()
+            .trim_start_matches("<!--")
+            .trim_end_matches("-->")
+            .trim()
+            .trim_start_matches("<![CDATA[")
+            .trim_end_matches("]]>");
+        match serde_json::from_str::<Value>(text) {
+            Ok(Value::Array(values)) => blocks.extend(values),
+            Ok(value) => blocks.push(value),
+            Err(_) => continue,
+        }
+    }
+    blocks
+}
+
+/// Parses the top-level microdata items of the page.
+pub fn parse_microdata(document: &Html) -> Vec<MicrodataItem> {
+    let scope_selector = Selector::parse("[itemscope]").unwrap();
+    document
+        .select(&scope_selector)
+        .filter(|element| element.value().attr("itemprop").is_none())
+        .map(microdata_item)
+        .collect()
+}
+
+fn microdata_item(scope: ElementRef) -> MicrodataItem {
+    let mut properties: BTreeMap<String, Vec<MicrodataValue>> = BTreeMap::new();
+    collect_properties(scope, &mut properties);
+    MicrodataItem {
+        types: scope
+            .value()
+            .attr("itemtype")
+            .map(|types| types.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default(),
+        properties,
+    }
+}
+
+// Propriétés d'un item, sans descendre dans les items imbriqués
+fn collect_properties(element: ElementRef, properties: &mut BTreeMap<String, Vec<MicrodataValue>>) {
+    for child in element.children().filter_map(ElementRef::wrap) {
+        let nested_scope = child.value().attr("itemscope").is_some();
+        if let Some(names) = child.value().attr("itemprop") {
+            let value = if nested_scope {
+                MicrodataValue::Item(microdata_item(child))
+            } else {
+                MicrodataValue::Text(property_value(child))
+            };
+            for name in names.split_whitespace() {
+                properties.entry(name.to_string()).or_default().push(value.clone());
+            }
+        }
+        if !nested_scope {
+            collect_properties(child, properties);
+        }
+    }
+}
+
+// Valeur d'une propriété selon la balise qui la porte
+fn property_value(element: ElementRef) -> String {
+    let value = element.value();
+    let attribute = match value.name() {
+        "meta" => value.attr("content"),
+        "a" | "link" | "area" => value.attr("href"),
+        "img" | "audio" | "video" | "source" | "iframe" | "embed" => value.attr("src"),
+        "object" => value.attr("data"),
+        "time" => value.attr("datetime"),
+        "data" | "meter" => value.attr("value"),
+        _ => None,
+    };
+    attribute.map(str::to_string).unwrap_or_else(|| {
+        element
+            .text()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
+
+/// Lists the schema.org types used by the JSON-LD blocks and microdata items,
+/// nested ones included, without their `https://schema.org/` prefix.
+pub fn schema_types(json_ld: &[Value], microdata: &[MicrodataItem]) -> Vec<String> {
+    let mut types = Vec::new();
+    for value in json_ld {
+        json_ld_types(value, &mut types);
+    }
+    for item in microdata {
+        microdata_types(item, &mut types);
+    }
+    types.sort();
+    types.dedup();
+    types
+}
+
+fn json_ld_types(value: &Value, types: &mut Vec<String>) {
+    match value {
+        Value::Object(object) => {
+            match object.get("@type") {
+                Some(Value::String(kind)) => types.push(schema_type_name(kind)),
+                Some(Value::Array(kinds)) => types.extend(kinds.iter().filter_map(Value::as_str).map(schema_type_name)),
+                _ => {}
+            }
+            for (key, child) in object {
+                if key != "@type" {
+                    json_ld_types(child, types);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for child in values {
+                json_ld_types(child, types);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn microdata_types(item: &MicrodataItem, types: &mut Vec<String>) {
+    types.extend(item.types.iter().map(|kind| schema_type_name(kind)));
+    for value in item.properties.values().flatten() {
+        if let MicrodataValue::Item(child) = value {
+            microdata_types(child, types);
+        }
+    }
+}
+
+// « https://schema.org/Course » → « Course »
+fn schema_type_name(kind: &str) -> String {
+    let kind = kind.trim().trim_end_matches('/');
+    kind.rsplit(['/', '#', ':']).next().unwrap_or(kind).to_string()
+}
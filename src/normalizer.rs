@@ -0,0 +1,61 @@
+use serde::Deserialize;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Unicode normalization form applied to the content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnicodeForm {
+    /// Canonical composition: "e" + combining acute accent gives "é".
+    #[default]
+    Nfc,
+    /// Compatibility composition: also folds "ﬁ" into "fi", "²" into "2"...
+    Nfkc,
+    /// Content is kept as written.
+    None,
+}
+
+/// Normalizes a text to the given Unicode form.
+///
+/// Invisible characters (zero-width spaces and joiners, soft hyphens, byte
+/// order marks) are removed and non-breaking spaces become regular spaces,
+/// so "cou\u{AD}rir" and "cou\u{200B}rir" are counted as "courir".
+pub fn normalize_unicode(text: &str, form: UnicodeForm) -> String {
+    let cleaned = text
+        .chars()
+        .filter(|&c| !is_invisible(c))
+        .map(|c| if is_non_breaking_space(c) { ' ' } else { c });
+    match form {
+        UnicodeForm::Nfc => cleaned.nfc().collect(),
+        UnicodeForm::Nfkc => cleaned.nfkc().collect(),
+        UnicodeForm::None => cleaned.collect(),
+    }
+}
+
+/// Removes the diacritics of a word and expands the "œ" and "æ" ligatures.
+///
+/// "élève" gives "eleve" and "cœur" gives "coeur".
+pub fn fold_diacritics(word: &str) -> String {
+    let mut folded = String::with_capacity(word.len());
+    for c in word.nfd().filter(|&c| !is_combining_mark(c)) {
+        match c {
+            'œ' => folded.push_str("oe"),
+            'Œ' => folded.push_str("OE"),
+            'æ' => folded.push_str("ae"),
+            'Æ' => folded.push_str("AE"),
+            _ => folded.push(c),
+        }
+    }
+    folded.nfc().collect()
+}
+
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}' | '\u{180E}' | '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}'
+    )
+}
+
+fn is_non_breaking_space(c: char) -> bool {
+    matches!(c, '\u{00A0}' | '\u{2007}' | '\u{202F}')
+}
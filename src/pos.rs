@@ -0,0 +1,231 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+use crate::entity::is_number;
+use crate::language::Language;
+use crate::tokenizer::Token;
+
+// Suffixes des mots absents du lexique : le suffixe le plus long l'emporte
+// (« -alement » fait un adverbe, « -ment » un nom)
+const FRENCH_SUFFIXES: &[(&str, PosTag)] = &[
+    ("amment", PosTag::Adv),
+    ("emment", PosTag::Adv),
+    ("ément", PosTag::Adv),
+    ("iment", PosTag::Adv),
+    ("ument", PosTag::Adv),
+    ("llement", PosTag::Adv),
+    ("ivement", PosTag::Adv),
+    ("usement", PosTag::Adv),
+    ("alement", PosTag::Adv),
+    ("quement", PosTag::Adv),
+    ("ièrement", PosTag::Adv),
+    ("tion", PosTag::Noun),
+    ("sion", PosTag::Noun),
+    ("isme", PosTag::Noun),
+    ("ité", PosTag::Noun),
+    ("ités", PosTag::Noun),
+    ("eur", PosTag::Noun),
+    ("eurs", PosTag::Noun),
+    ("age", PosTag::Noun),
+    ("ages", PosTag::Noun),
+    ("ance", PosTag::Noun),
+    ("ence", PosTag::Noun),
+    ("ure", PosTag::Noun),
+    ("ment", PosTag::Noun),
+    ("ments", PosTag::Noun),
+    ("able", PosTag::Adj),
+    ("ible", PosTag::Adj),
+    ("ique", PosTag::Adj),
+    ("iques", PosTag::Adj),
+    ("aire", PosTag::Adj),
+    ("aires", PosTag::Adj),
+    ("if", PosTag::Adj),
+    ("ive", PosTag::Adj),
+    ("ifs", PosTag::Adj),
+    ("ives", PosTag::Adj),
+    ("eux", PosTag::Adj),
+    ("euse", PosTag::Adj),
+    ("al", PosTag::Adj),
+    ("ale", PosTag::Adj),
+    ("aux", PosTag::Adj),
+    ("ales", PosTag::Adj),
+    ("el", PosTag::Adj),
+    ("elle", PosTag::Adj),
+    ("els", PosTag::Adj),
+    ("elles", PosTag::Adj),
+    ("é", PosTag::Adj),
+    ("ée", PosTag::Adj),
+    ("és", PosTag::Adj),
+    ("ées", PosTag::Adj),
+    ("er", PosTag::Verb),
+    ("ir", PosTag::Verb),
+    ("ez", PosTag::Verb),
+    ("ons", PosTag::Verb),
+    ("ait", PosTag::Verb),
+    ("aient", PosTag::Verb),
+];
+
+const ENGLISH_SUFFIXES: &[(&str, PosTag)] = &[
+    ("ly", PosTag::Adv),
+    ("tion", PosTag::Noun),
+    ("sion", PosTag::Noun),
+    ("ness", PosTag::Noun),
+    ("ment", PosTag::Noun),
+    ("ity", PosTag::Noun),
+    ("ism", PosTag::Noun),
+    ("ist", PosTag::Noun),
+    ("ship", PosTag::Noun),
+    ("ance", PosTag::Noun),
+    ("ence", PosTag::Noun),
+    ("er", PosTag::Noun),
+    ("or", PosTag::Noun),
+    ("able", PosTag::Adj),
+    ("ible", PosTag::Adj),
+    ("ful", PosTag::Adj),
+    ("less", PosTag::Adj),
+    ("ous", PosTag::Adj),
+    ("ive", PosTag::Adj),
+    ("al", PosTag::Adj),
+    ("ic", PosTag::Adj),
+    ("ish", PosTag::Adj),
+    ("ary", PosTag::Adj),
+    ("ing", PosTag::Verb),
+    ("ed", PosTag::Verb),
+    ("ize", PosTag::Verb),
+    ("ise", PosTag::Verb),
+    ("ify", PosTag::Verb),
+];
+
+/// Part-of-speech tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PosTag {
+    Noun,
+    Verb,
+    Adj,
+    Adv,
+    Det,
+    Pron,
+    Prep,
+    Conj,
+    Num,
+}
+
+impl PosTag {
+    /// Parses a tag name such as `NOUN` or `PREP`.
+    pub fn parse(name: &str) -> Option<PosTag> {
+        match name.to_uppercase().as_str() {
+            "NOUN" => Some(PosTag::Noun),
+            "VERB" => Some(PosTag::Verb),
+            "ADJ" => Some(PosTag::Adj),
+            "ADV" => Some(PosTag::Adv),
+            "DET" => Some(PosTag::Det),
+            "PRON" => Some(PosTag::Pron),
+            "PREP" => Some(PosTag::Prep),
+            "CONJ" => Some(PosTag::Conj),
+            "NUM" => Some(PosTag::Num),
+            _ => None,
+        }
+    }
+}
+
+/// Sequence of tags an n-gram must follow, written "NOUN PREP NOUN".
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct PosPattern(Vec<PosTag>);
+
+impl PosPattern {
+    pub fn matches(&self, tags: &[PosTag]) -> bool {
+        self.0 == tags
+    }
+}
+
+impl TryFrom<String> for PosPattern {
+    type Error = String;
+
+    fn try_from(pattern: String) -> Result<Self, Self::Error> {
+        let tags = pattern
+            .split_whitespace()
+            .map(|name| {
+                PosTag::parse(name)
+                    .ok_or_else(|| format!("Étiquette morphosyntaxique inconnue : {}", name))
+            })
+            .collect::<Result<Vec<PosTag>, String>>()?;
+        if tags.is_empty() {
+            return Err("Motif morphosyntaxique vide".to_string());
+        }
+        Ok(PosPattern(tags))
+    }
+}
+
+/// Offline part-of-speech tagger.
+///
+/// Words are looked up in the lexicon of the language; unknown words are
+/// tagged from their suffix ("-tion" is a noun, "-able" an adjective...) and
+/// default to nouns. Numbers and typed entities are tagged `NUM`, a verb
+/// guessed from its suffix right after a determiner is a noun ("le devoir"),
+/// and the French "des" after a noun is a preposition ("professeur des écoles").
+#[derive(Debug, Clone)]
+pub struct PosTagger {
+    language: Language,
+    lexicon: HashMap<String, PosTag>,
+}
+
+impl PosTagger {
+    pub fn load(language: Language) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(language.pos_lexicon_path())?;
+        let mut lexicon = HashMap::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (word, tag) = line
+                .rsplit_once(char::is_whitespace)
+                .and_then(|(word, name)| Some((word.trim(), PosTag::parse(name)?)))
+                .ok_or_else(|| {
+                    format!("Entrée de lexique invalide à la ligne {} : {}", number + 1, line)
+                })?;
+            lexicon.insert(word.to_lowercase(), tag);
+        }
+        Ok(PosTagger { language, lexicon })
+    }
+
+    /// Tags every token, using the form it had before stemming.
+    pub fn tag_tokens(&self, tokens: &[Token]) -> Vec<PosTag> {
+        let mut tags: Vec<PosTag> = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            let word = token.form().to_lowercase();
+            let tag = if token.entity.is_some() || is_number(&word) {
+                PosTag::Num
+            } else if word == "des"
+                && self.language == Language::French
+                && tags.last() == Some(&PosTag::Noun)
+            {
+                PosTag::Prep
+            } else if let Some(&tag) = self.lexicon.get(&word) {
+                tag
+            } else {
+                match self.suffix_tag(&word) {
+                    PosTag::Verb if tags.last() == Some(&PosTag::Det) => PosTag::Noun,
+                    tag => tag,
+                }
+            };
+            tags.push(tag);
+        }
+        tags
+    }
+
+    fn suffix_tag(&self, word: &str) -> PosTag {
+        let suffixes = match self.language {
+            Language::French => FRENCH_SUFFIXES,
+            Language::English => ENGLISH_SUFFIXES,
+        };
+        suffixes
+            .iter()
+            .filter(|(suffix, _)| word.ends_with(suffix) && word.len() > suffix.len() + 1)
+            .max_by_key(|(suffix, _)| suffix.chars().count())
+            .map_or(PosTag::Noun, |&(_, tag)| tag)
+    }
+}
@@ -0,0 +1,100 @@
+use crate::language::Language;
+
+/// Readability of a document.
+///
+/// `reading_ease` is the Flesch Reading Ease for English and its Kandel–Moles
+/// adaptation for French (0-100, higher is easier). `grade_level` is the
+/// Flesch–Kincaid grade level, computed with the syllables of the language.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Readability {
+    pub words_per_sentence: f64,
+    pub syllables_per_word: f64,
+    pub reading_ease: f64,
+    pub grade_level: f64,
+}
+
+impl Readability {
+    pub fn compute(language: Language, sentences: usize, words: usize, syllables: usize) -> Self {
+        if sentences == 0 || words == 0 {
+            return Readability::default();
+        }
+
+        let words_per_sentence = words as f64 / sentences as f64;
+        let syllables_per_word = syllables as f64 / words as f64;
+        let reading_ease = match language {
+            Language::English => 206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word,
+            Language::French => 207.0 - 1.015 * words_per_sentence - 73.6 * syllables_per_word,
+        };
+
+        Readability {
+            words_per_sentence,
+            syllables_per_word,
+            reading_ease,
+            grade_level: 0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59,
+        }
+    }
+}
+
+/// Name of the reading ease formula used for a language.
+pub fn reading_ease_formula(language: Language) -> &'static str {
+    match language {
+        Language::English => "flesch",
+        Language::French => "kandel_moles",
+    }
+}
+
+/// Estimates the number of syllables of a word.
+///
+/// Syllables are the groups of consecutive vowels. A final mute "e" ("e"/"es"
+/// in French, "e"/"es"/"ed" in English) does not count, and every word has at
+/// least one syllable.
+pub fn count_syllables(word: &str, language: Language) -> usize {
+    let word = word.to_lowercase();
+    let chars: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+    if chars.is_empty() {
+        return 0;
+    }
+
+    let is_vowel = |c: char| match language {
+        Language::French => "aeiouyàâäéèêëîïôöùûüÿœæ".contains(c),
+        Language::English => "aeiouy".contains(c),
+    };
+
+    let mut groups = 0;
+    let mut previous_vowel = false;
+    for &c in &chars {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            groups += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    // Position d'un « e » final muet (« école », « élèves », « make », « jumped »)
+    let word: String = chars.iter().collect();
+    let ends_with_any = |endings: &[&str]| endings.iter().any(|ending| word.ends_with(ending));
+    let mute_e = match language {
+        Language::French if word.ends_with("es") => Some(chars.len() - 2),
+        Language::French if word.ends_with('e') => Some(chars.len() - 1),
+        Language::English if word.ends_with("ed") && !ends_with_any(&["ted", "ded"]) => {
+            Some(chars.len() - 2)
+        }
+        Language::English
+            if word.ends_with("es")
+                && !ends_with_any(&["ses", "xes", "zes", "ches", "shes", "ges", "ces"]) =>
+        {
+            Some(chars.len() - 2)
+        }
+        Language::English if word.ends_with('e') && !ends_with_any(&["le", "ee"]) => {
+            Some(chars.len() - 1)
+        }
+        _ => None,
+    };
+    if let Some(index) = mute_e {
+        if groups > 1 && index > 0 && !is_vowel(chars[index - 1]) {
+            groups -= 1;
+        }
+    }
+
+    groups.max(1)
+}
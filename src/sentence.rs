@@ -0,0 +1,121 @@
+use std::ops::Range;
+
+// Abréviations françaises et anglaises suivies d'un point qui ne termine pas la phrase
+const ABBREVIATIONS: &[&str] = &[
+    // Français
+    "m", "mme", "mmes", "mlle", "mlles", "mgr", "dr", "pr", "st", "ste", "cf", "av", "bd",
+    "fg", "chap", "fig", "éd", "coll", "tél", "hab", "apr", "janv", "févr", "avr", "juil",
+    "oct", "nov", "déc", "nb", "réf",
+    // Anglais
+    "mr", "mrs", "ms", "prof", "sr", "jr", "vs", "inc", "ltd", "corp", "dept", "approx",
+    "jan", "feb", "jun", "jul", "aug", "sep", "dec", "gov",
+];
+
+// Abréviations qui sont aussi des mots courants (« l'art. », « ils étaient sept. ») :
+// le point ne termine pas la phrase seulement devant un nombre ou une minuscule
+const AMBIGUOUS_ABBREVIATIONS: &[&str] = &[
+    "art", "sept", "vol", "min", "max", "co", "ex", "mar", "gen", "sen", "rev", "env", "mm",
+];
+
+/// Splits a text into sentences and returns their byte spans.
+///
+/// A sentence ends with `.`, `!`, `?` or `…` followed by a space, or with an
+/// empty line. A period does not end the sentence after a known abbreviation
+/// ("M. Dupont", "Dr. Smith"), an initial ("J. K. Rowling") or inside a
+/// number ("3.14", "99.5"). Abbreviations that are also common words ("art",
+/// "sept", "min") only count as such before a number or a lowercase word
+/// ("art. 12", "5 min. plus tard"). Spans exclude surrounding whitespace.
+pub fn segment_sentences(text: &str) -> Vec<Range<usize>> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let position = |i: usize| chars.get(i).map_or(text.len(), |&(pos, _)| pos);
+
+    let mut sentences = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let (pos, c) = chars[i];
+        if start.is_none() && !c.is_whitespace() {
+            start = Some(pos);
+        }
+
+        if is_terminator(c) {
+            // Ponctuation finale et guillemets ou parenthèses fermants qui la suivent
+            let mut j = i + 1;
+            while j < chars.len() && (is_terminator(chars[j].1) || is_closing(chars[j].1)) {
+                j += 1;
+            }
+            let followed_by_space = j == chars.len() || chars[j].1.is_whitespace();
+            let abbreviation = c == '.' && j == i + 1 && is_abbreviation(&text[..pos], &text[position(j)..]);
+            if followed_by_space && !abbreviation {
+                push_sentence(text, &mut sentences, start.take(), position(j));
+            }
+            i = j;
+            continue;
+        }
+
+        // Une ligne vide sépare deux paragraphes
+        if c == '\n' {
+            let mut j = i + 1;
+            while j < chars.len() && chars[j].1.is_whitespace() && chars[j].1 != '\n' {
+                j += 1;
+            }
+            if chars.get(j).is_some_and(|(_, next)| *next == '\n') {
+                push_sentence(text, &mut sentences, start.take(), pos);
+            }
+        }
+        i += 1;
+    }
+    push_sentence(text, &mut sentences, start, text.len());
+
+    sentences
+}
+
+fn push_sentence(text: &str, sentences: &mut Vec<Range<usize>>, start: Option<usize>, end: usize) {
+    let Some(start) = start else {
+        return;
+    };
+    let end = start + text[start..end].trim_end().len();
+    if text[start..end].chars().any(char::is_alphanumeric) {
+        sentences.push(start..end);
+    }
+}
+
+fn is_terminator(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '…')
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(c, ')' | ']' | '"' | '\'' | '»' | '”' | '’')
+}
+
+// Vrai si le mot qui précède le point est une abréviation ou une initiale
+fn is_abbreviation(before: &str, after: &str) -> bool {
+    let word: String = before
+        .chars()
+        .rev()
+        .take_while(|c| c.is_alphabetic() || *c == '.' || *c == '-')
+        .collect::<Vec<char>>()
+        .into_iter()
+        .rev()
+        .collect::<String>()
+        .to_lowercase();
+    let parts: Vec<&str> = word.split(['.', '-']).filter(|part| !part.is_empty()).collect();
+    let Some(last) = parts.last() else {
+        return false;
+    };
+
+    // Initiale (« J. »), sigle pointé (« e.g. », « U.S. ») ou abréviation connue
+    last.chars().count() == 1
+        || (parts.len() > 1 && parts.iter().all(|part| part.chars().count() == 1))
+        || ABBREVIATIONS.contains(last)
+        || (AMBIGUOUS_ABBREVIATIONS.contains(last) && continues_sentence(after))
+}
+
+// Vrai si le texte après le point commence par un chiffre ou une minuscule
+fn continues_sentence(after: &str) -> bool {
+    after
+        .trim_start()
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || c.is_lowercase())
+}
@@ -0,0 +1,56 @@
+use rust_stemmers::Algorithm;
+use serde::Deserialize;
+use std::fmt;
+
+use crate::language::Language;
+
+/// Normalization applied to tokens before counting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalizationMode {
+    /// Tokens are counted as written.
+    #[default]
+    None,
+    /// Inflected forms are merged under their Snowball stem.
+    Stem,
+}
+
+/// Snowball stemmer for a given language.
+///
+/// "professeur", "professeurs" and "professeure" all give "professeur".
+pub struct Stemmer {
+    language: Language,
+    inner: rust_stemmers::Stemmer,
+}
+
+impl Stemmer {
+    pub fn new(language: Language) -> Self {
+        let algorithm = match language {
+            Language::French => Algorithm::French,
+            Language::English => Algorithm::English,
+        };
+        Stemmer {
+            language,
+            inner: rust_stemmers::Stemmer::create(algorithm),
+        }
+    }
+
+    pub fn stem(&self, word: &str) -> String {
+        self.inner.stem(word).into_owned()
+    }
+}
+
+// Le raciniseur de rust_stemmers n'est pas clonable : il est recréé pour la même langue
+impl Clone for Stemmer {
+    fn clone(&self) -> Self {
+        Stemmer::new(self.language)
+    }
+}
+
+impl fmt::Debug for Stemmer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stemmer")
+            .field("language", &self.language)
+            .finish()
+    }
+}
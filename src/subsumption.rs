@@ -0,0 +1,55 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// What to do with an expression subsumed by a longer one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsumptionMode {
+    /// The expression is removed from the results.
+    #[default]
+    Drop,
+    /// The expression is kept, with the longer expression that subsumes it.
+    Mark,
+}
+
+/// Finds the expressions whose count is almost fully explained by a single
+/// longer expression containing them.
+///
+/// `expressions` holds (expression, count) pairs, words separated by spaces.
+/// An expression is subsumed when a longer expression containing its words
+/// contiguously has at least `threshold` times its count: with a threshold
+/// of 0.9, "professeur des" seen 12 times is subsumed by "professeur des
+/// écoles" seen 11 times. Returns, for each subsumed index, the index of the
+/// most frequent longer expression containing it.
+pub fn find_subsumed(expressions: &[(&str, f64)], threshold: f64) -> HashMap<usize, usize> {
+    let index: HashMap<&str, usize> = expressions
+        .iter()
+        .enumerate()
+        .map(|(i, (expression, _))| (*expression, i))
+        .collect();
+
+    // Pour chaque expression, l'expression plus longue la contenant la plus fréquente
+    let mut best_container: HashMap<usize, usize> = HashMap::new();
+    for (longer, (expression, count)) in expressions.iter().enumerate() {
+        let words: Vec<&str> = expression.split(' ').collect();
+        for size in 1..words.len() {
+            for window in words.windows(size) {
+                let Some(&shorter) = index.get(window.join(" ").as_str()) else {
+                    continue;
+                };
+                let best = best_container.entry(shorter).or_insert(longer);
+                if *count > expressions[*best].1 {
+                    *best = longer;
+                }
+            }
+        }
+    }
+
+    best_container
+        .into_iter()
+        .filter(|&(shorter, longer)| {
+            let shorter_count = expressions[shorter].1;
+            shorter_count > 0.0 && expressions[longer].1 >= threshold * shorter_count
+        })
+        .collect()
+}
@@ -1,6 +1,25 @@
 #[cfg(test)]
 mod tests {
-    use crate::text_analyzer::TextAnalyzer;
+    use crate::api::{
+        analyze_document, process_frequencies, protected_expressions, tf_idf, AnalysisOptions, AnalysisRequest,
+        AnalyzerCache, StoredDocument,
+    };
+    use crate::entity::{EntityKind, EntityMode};
+    use crate::inclusive::{canonicalize_inclusive, InclusiveForm, InclusiveMode};
+    use crate::language::{detect_language, Language};
+    use crate::links::{LinkKind, LinkStats};
+    use crate::metadata::{MicrodataValue, PageMetadata};
+    use crate::readability::count_syllables;
+    use crate::sentence::segment_sentences;
+    use crate::normalizer::{fold_diacritics, normalize_unicode, UnicodeForm};
+    use crate::pos::{PosPattern, PosTag, PosTagger};
+    use crate::stemmer::Stemmer;
+    use crate::subsumption::find_subsumed;
+    use crate::text_analyzer::{StopWordPolicy, TextAnalyzer};
+    use crate::tokenizer::{Tokenizer, TokenizerKind, UnicodeTokenizer};
+    use crate::web_analyzer::{ContentExtraction, Heading, WebAnalyzer};
+    use crate::zone::{Zone, ZoneWeights};
+    use std::collections::{HashMap, HashSet};
 
     #[test]
     fn test_word_count() {
@@ -238,4 +257,1107 @@ mod tests {
             fourgrams_count
         );
     }
+
+    #[test]
+    fn test_ngrams_beyond_five() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from(
+            "professeur agrégé enseignant mathématiques lycée général professeur agrégé enseignant mathématiques lycée général",
+        );
+        analyzer.count_words();
+
+        for n in [6, 7, 8] {
+            analyzer.word_frequency_ngrams(n);
+            let (frequencies, percentages) = analyzer._get_ngram_frequency(n).unwrap();
+            assert_eq!(frequencies.len(), *analyzer.retained_expressions.get(&n).unwrap());
+            assert_eq!(frequencies.len(), percentages.len());
+        }
+
+        let (sixgrams, _) = analyzer._get_ngram_frequency(6).unwrap();
+        assert_eq!(
+            sixgrams.get("professeur agrégé enseignant mathématiques lycée général"),
+            Some(&2)
+        );
+        assert!(analyzer._get_ngram_frequency(9).is_none());
+    }
+
+    #[test]
+    fn test_unicode_tokenizer() {
+        let text = "Le prix est de 99,99€. Écrivez à contact@example.fr ou sur https://example.fr/page, allez-vous acheter un porte-monnaie ?";
+        let tokens = UnicodeTokenizer.tokenize(text);
+        let words: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(
+            words,
+            vec![
+                "Le", "prix", "est", "de", "99,99", "Écrivez", "à", "contact@example.fr", "ou",
+                "sur", "https://example.fr/page", "allez", "vous", "acheter", "un",
+                "porte-monnaie",
+            ]
+        );
+        for token in &tokens {
+            assert_eq!(&text[token.start..token.end], token.text);
+        }
+    }
+
+    #[test]
+    fn test_counting_uses_selected_tokenizer() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from("porte-monnaie porte-monnaie cuir");
+        analyzer.count_words();
+        analyzer.word_frequency_ngrams(1);
+        assert_eq!(analyzer.count_words(), 3);
+        assert_eq!(analyzer._count_word_frequency(&"porte-monnaie".to_string()), 2);
+
+        analyzer.set_tokenizer(TokenizerKind::WordBoundary.build());
+        analyzer.word_frequency_ngrams(1);
+        assert_eq!(analyzer.count_words(), 5);
+        assert_eq!(analyzer._count_word_frequency(&"monnaie".to_string()), 2);
+        assert_eq!(analyzer._count_word_frequency(&"porte-monnaie".to_string()), 0);
+    }
+
+    #[test]
+    fn test_stemming_merges_inflected_forms() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from(
+            "le professeur et les professeurs parlent à la professeure des écoles",
+        );
+        analyzer.set_stemmer(Some(Stemmer::new(Language::French)));
+        analyzer.count_words();
+        analyzer.word_frequency_ngrams(1);
+
+        assert_eq!(analyzer._count_word_frequency(&"professeur".to_string()), 3);
+        assert_eq!(analyzer._count_word_frequency(&"professeurs".to_string()), 0);
+        // Les mots vides sont reconnus sur leur forme d'origine
+        assert_eq!(analyzer._count_word_frequency(&"le".to_string()), 0);
+
+        let forms = analyzer.get_surface_forms(1).unwrap().get("professeur").unwrap();
+        assert_eq!(forms.len(), 3);
+        assert_eq!(forms.get("professeurs"), Some(&1));
+        assert_eq!(forms.get("professeure"), Some(&1));
+    }
+
+    #[test]
+    fn test_english_stemming() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_english.txt").unwrap();
+        analyzer.content = String::from("teachers teaching teacher");
+        analyzer.set_stemmer(Some(Stemmer::new(Language::English)));
+        analyzer.word_frequency_ngrams(1);
+        assert_eq!(analyzer._count_word_frequency(&"teacher".to_string()), 2);
+        assert_eq!(analyzer._count_word_frequency(&"teach".to_string()), 1);
+    }
+
+    #[test]
+    fn test_detect_language() {
+        let french = "Le professeur des écoles enseigne toutes les matières à ses élèves, \
+            de la maternelle jusqu'au CM2, et prépare chaque jour ses leçons.";
+        let english = "The school teacher teaches every subject to the pupils \
+            and prepares the lessons carefully every single day of the week.";
+        assert_eq!(detect_language(french, None), Language::French);
+        assert_eq!(detect_language(english, Some("fr")), Language::English);
+
+        // Sans texte exploitable, l'attribut lang de la page est utilisé
+        assert_eq!(detect_language("", Some("en-US")), Language::English);
+        assert_eq!(detect_language("", None), Language::French);
+        assert_eq!(Language::from_tag("fr_FR"), Some(Language::French));
+        assert_eq!(Language::from_tag("de"), None);
+    }
+
+    #[test]
+    fn test_tf_idf_smoothed_idf() {
+        // Terme présent dans un document sur deux : idf = ln(3 / 2) + 1
+        let expected = 2.0 / 10.0 * ((3.0_f64 / 2.0).ln() + 1.0);
+        assert!((tf_idf(2, 10, 1, 2) - expected).abs() < 1e-12);
+        // Terme présent partout : idf = 1, le TF-IDF se réduit à la fréquence relative
+        assert!((tf_idf(2, 10, 2, 2) - 0.2).abs() < 1e-12);
+        assert_eq!(tf_idf(2, 0, 1, 2), 0.0);
+    }
+
+    #[test]
+    fn test_sort_by_tf_idf() {
+        let request = serde_json::from_value(serde_json::json!({
+            "urls": [],
+            "ngrams_to_analyze": [1],
+            "sort_by": "tf_idf"
+        }))
+        .unwrap();
+        let options = AnalysisOptions::from_request(&request);
+
+        let mut analyzers = AnalyzerCache::default();
+        let mut frequencies = HashMap::new();
+        let mut doc_stats = Vec::new();
+        let pages = [
+            ("https://a.example", "concours inspé concours inspé"),
+            ("https://b.example", "concours formation concours formation"),
+        ];
+        for (url, content) in pages {
+            let document = StoredDocument {
+                url: url.to_string(),
+                content: content.to_string(),
+                language: Language::French,
+                zones: HashMap::new(),
+                outline: Vec::new(),
+                metadata: PageMetadata::default(),
+                links: Vec::new(),
+            };
+            analyze_document(&document, &options, &mut analyzers, &mut frequencies, &mut doc_stats).unwrap();
+        }
+
+        let results = serde_json::to_value(process_frequencies(frequencies, 2, &options, &HashSet::new())).unwrap();
+        let results = results.as_array().unwrap();
+        let position = |expression: &str| {
+            results
+                .iter()
+                .position(|result| result["expression"] == expression)
+                .unwrap()
+        };
+        // « concours » est présent dans toutes les pages, « inspé » est propre à une page
+        assert!(position("inspé") < position("concours"));
+
+        let inspe = &results[position("inspé")];
+        let expected = (0.5 * ((3.0_f64 / 2.0).ln() + 1.0) * 10000.0).round() / 10000.0;
+        assert_eq!(inspe["tf_idf"], expected);
+        assert_eq!(inspe["tf_idf_by_source"][0]["url"], "https://a.example");
+        assert_eq!(results[position("concours")]["tf_idf"], 0.5);
+    }
+
+    #[test]
+    fn test_protected_terms_survive_subsumption() {
+        let request = |protected: &[&str]| -> AnalysisRequest {
+            serde_json::from_value(serde_json::json!({
+                "urls": [],
+                "ngrams_to_analyze": [2, 3],
+                "normalization": "stem",
+                "fold_diacritics": true,
+                "subsumption_threshold": 0.9,
+                "subsumption_mode": "drop",
+                "protected_terms": protected
+            }))
+            .unwrap()
+        };
+        let expressions = |protected: &[&str]| {
+            let options = AnalysisOptions::from_request(&request(protected));
+            let mut analyzers = AnalyzerCache::default();
+            let mut frequencies = HashMap::new();
+            let document = StoredDocument {
+                url: "https://a.example".to_string(),
+                content: "Écoles publiques primaires. L'école publique primaire.".to_string(),
+                language: Language::French,
+                zones: HashMap::new(),
+                outline: Vec::new(),
+                metadata: PageMetadata::default(),
+                links: Vec::new(),
+            };
+            analyze_document(&document, &options, &mut analyzers, &mut frequencies, &mut Vec::new()).unwrap();
+            let protected = protected_expressions(&[document], &options, &mut analyzers);
+            process_frequencies(frequencies, 1, &options, &protected)
+                .into_iter()
+                .map(|result| serde_json::to_value(result).unwrap()["expression"].as_str().unwrap().to_string())
+                .collect::<Vec<String>>()
+        };
+
+        assert!(!expressions(&[]).contains(&"ecol publiqu".to_string()));
+        let protected = expressions(&["École  publique"]);
+        assert!(protected.contains(&"ecol publiqu".to_string()));
+        assert!(protected.contains(&"ecol publiqu primair".to_string()));
+    }
+
+    #[test]
+    fn test_subsumption_threshold_validation() {
+        let request = |threshold: f64| -> AnalysisRequest {
+            serde_json::from_value(serde_json::json!({ "urls": [], "subsumption_threshold": threshold })).unwrap()
+        };
+        assert!(request(0.9).validate().is_ok());
+        assert!(request(1.0).validate().is_ok());
+        for threshold in [0.0, -0.5, 1.5] {
+            assert!(request(threshold).validate().is_err(), "{}", threshold);
+        }
+    }
+
+    #[test]
+    fn test_collocation_scores() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from(
+            "tarte tatin maison, pomme rouge, tarte tatin fine, pomme verte, \
+             pomme jaune, tarte tatin chaude, pomme cuite, tarte tatin froide, pomme tatin",
+        );
+        analyzer.count_words();
+        analyzer.word_frequency_ngrams(2);
+        let scores = analyzer.collocation_scores(2);
+
+        let tarte_tatin = scores.get("tarte tatin").unwrap();
+        let pomme_tatin = scores.get("pomme tatin").unwrap();
+        assert!(tarte_tatin.pmi > pomme_tatin.pmi);
+        assert!(tarte_tatin.log_likelihood > pomme_tatin.log_likelihood);
+        assert!(tarte_tatin.t_score > pomme_tatin.t_score);
+        assert!(analyzer.collocation_scores(1).is_empty());
+    }
+
+    #[test]
+    fn test_rake_keyphrases() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_english.txt").unwrap();
+        analyzer.content = String::from(
+            "Compatibility of systems of linear constraints over the set of natural numbers. \
+             Criteria of compatibility of a system of linear Diophantine equations are considered.",
+        );
+        analyzer.analyze();
+        let keyphrases = analyzer.rake_keyphrases();
+        let phrases: Vec<&str> = keyphrases.iter().map(|k| k.phrase.as_str()).collect();
+
+        assert_eq!(phrases[0], "linear diophantine equations");
+        assert!(phrases.contains(&"natural numbers"));
+        assert!(phrases.contains(&"linear constraints"));
+        // La ponctuation sépare les expressions candidates
+        assert!(!phrases.iter().any(|p| p.contains("numbers criteria")));
+
+        let compatibility = keyphrases.iter().find(|k| k.phrase == "compatibility").unwrap();
+        assert_eq!(compatibility.occurrences, 2);
+        assert_eq!(compatibility.score, 1.0);
+    }
+
+    #[test]
+    fn test_textrank_keyphrases() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_english.txt").unwrap();
+        analyzer.content = String::from(
+            "Compatibility of systems of linear constraints over the set of natural numbers. \
+             Criteria of compatibility of a system of linear Diophantine equations, strict inequations, \
+             and nonstrict inequations are considered. Upper bounds for components of a minimal set \
+             of solutions and algorithms of construction of minimal generating sets of solutions \
+             for all types of systems are given.",
+        );
+        analyzer.analyze();
+        let keyphrases = analyzer.textrank_keyphrases();
+        let phrases: Vec<&str> = keyphrases.iter().map(|k| k.phrase.as_str()).collect();
+
+        assert_eq!(phrases[0], "minimal set");
+        assert!(phrases.contains(&"nonstrict inequations"));
+        assert!(phrases.contains(&"linear"));
+        assert!(keyphrases.windows(2).all(|w| w[0].score >= w[1].score));
+        // Les mots vides ne sont jamais retenus
+        assert!(!phrases.iter().any(|p| p.split(' ').any(|w| w == "of" || w == "the")));
+    }
+
+    #[test]
+    fn test_segment_sentences() {
+        let text = "M. Dupont a payé 99.90 euros, soit 3.5 % de plus. Le Dr. Smith \
+                    l'a confirmé ! Est-ce « vraiment » utile ? J. K. Rowling le pense…\n\nNouveau paragraphe";
+        let sentences: Vec<&str> = segment_sentences(text)
+            .into_iter()
+            .map(|span| &text[span])
+            .collect();
+        assert_eq!(
+            sentences,
+            vec![
+                "M. Dupont a payé 99.90 euros, soit 3.5 % de plus.",
+                "Le Dr. Smith l'a confirmé !",
+                "Est-ce « vraiment » utile ?",
+                "J. K. Rowling le pense…",
+                "Nouveau paragraphe",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_abbreviations() {
+        let split = |text: &str| -> Vec<String> {
+            segment_sentences(text).into_iter().map(|span| text[span].to_string()).collect()
+        };
+        assert_eq!(
+            split("J'aime beaucoup l'art. Le musée ouvre demain."),
+            vec!["J'aime beaucoup l'art.", "Le musée ouvre demain."]
+        );
+        assert_eq!(
+            split("Ils étaient sept. Puis huit sont venus."),
+            vec!["Ils étaient sept.", "Puis huit sont venus."]
+        );
+        assert_eq!(split("Il a raté son vol. Le suivant part à midi.").len(), 2);
+        assert_eq!(split("Attendez 5 min. Le bus arrive.").len(), 2);
+        // Devant un nombre ou une minuscule, ce sont des abréviations
+        assert_eq!(split("Voir l'art. 12 du code et le vol. 3 de la revue.").len(), 1);
+        assert_eq!(split("Il reste 5 min. avant le départ.").len(), 1);
+    }
+
+    #[test]
+    fn test_sentences_survive_pipeline() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from("M. Dupont enseigne à l'école. Les élèves l'apprécient.");
+        analyzer.analyze();
+        analyzer.normalize_apostrophes();
+        analyzer.clean_word();
+        let content = analyzer.content.clone();
+        let sentences: Vec<String> = analyzer
+            .sentences()
+            .iter()
+            .map(|span| content[span.clone()].to_string())
+            .collect();
+        assert_eq!(sentences, vec!["M. Dupont enseigne à école.", "Les élèves apprécient."]);
+    }
+
+    #[test]
+    fn test_count_syllables() {
+        assert_eq!(count_syllables("école", Language::French), 2);
+        assert_eq!(count_syllables("élèves", Language::French), 2);
+        assert_eq!(count_syllables("année", Language::French), 2);
+        assert_eq!(count_syllables("professeur", Language::French), 3);
+        assert_eq!(count_syllables("make", Language::English), 1);
+        assert_eq!(count_syllables("table", Language::English), 2);
+        assert_eq!(count_syllables("jumped", Language::English), 1);
+        assert_eq!(count_syllables("wanted", Language::English), 2);
+        assert_eq!(count_syllables("readability", Language::English), 5);
+    }
+
+    #[test]
+    fn test_readability() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_english.txt").unwrap();
+        analyzer.content = String::from("The cat sat on the mat. The dog ran to the park.");
+        let simple = analyzer.readability(Language::English);
+        assert_eq!(simple.words_per_sentence, 6.0);
+        assert_eq!(simple.syllables_per_word, 1.0);
+        assert!((simple.reading_ease - 116.145).abs() < 1e-9);
+
+        analyzer.content = String::from(
+            "Institutional accountability considerations necessitate comprehensive organizational restructuring.",
+        );
+        let complex = analyzer.readability(Language::English);
+        assert!(complex.reading_ease < simple.reading_ease);
+        assert!(complex.grade_level > simple.grade_level);
+
+        analyzer.content = String::new();
+        assert_eq!(analyzer.readability(Language::French).reading_ease, 0.0);
+    }
+
+    #[test]
+    fn test_stop_word_policy() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from(
+            "professeur des écoles en 2023 2024 professeur de la de école",
+        );
+        analyzer.count_words();
+
+        analyzer.word_frequency_ngrams(3);
+        let (trigrams, _) = analyzer._get_ngram_frequency(3).unwrap();
+        assert!(trigrams.contains_key("professeur des écoles"));
+        assert!(trigrams.contains_key("2023 2024 professeur"));
+
+        analyzer.set_stop_word_policy(StopWordPolicy::NoStopWords);
+        analyzer.word_frequency_ngrams(3);
+        let (trigrams, _) = analyzer._get_ngram_frequency(3).unwrap();
+        assert!(!trigrams.contains_key("professeur des écoles"));
+        assert!(trigrams.contains_key("2023 2024 professeur"));
+
+        analyzer.set_stop_word_policy(StopWordPolicy::MaxRatio { max_ratio: 0.5 });
+        analyzer.set_drop_numeric_tokens(true);
+        analyzer.word_frequency_ngrams(3);
+        let (trigrams, _) = analyzer._get_ngram_frequency(3).unwrap();
+        assert!(trigrams.contains_key("professeur des écoles"));
+        assert!(!trigrams.contains_key("2023 2024 professeur"));
+        analyzer.word_frequency_ngrams(5);
+        let (fivegrams, _) = analyzer._get_ngram_frequency(5).unwrap();
+        assert!(!fivegrams.contains_key("professeur de la de école"));
+    }
+
+    #[test]
+    fn test_find_subsumed() {
+        let expressions = vec![
+            ("professeur des écoles", 12.0),
+            ("professeur des", 12.0),
+            ("des écoles", 13.0),
+            ("professeur", 30.0),
+            ("écoles", 14.0),
+        ];
+        let subsumed = find_subsumed(&expressions, 0.9);
+
+        assert_eq!(subsumed.get(&1), Some(&0));
+        assert_eq!(subsumed.get(&2), Some(&0));
+        // « professeur » apparaît bien plus souvent que l'expression qui le contient
+        assert!(!subsumed.contains_key(&3));
+        assert_eq!(subsumed.get(&4), Some(&2));
+        assert!(!subsumed.contains_key(&0));
+
+        let strict = find_subsumed(&expressions, 1.0);
+        assert!(strict.contains_key(&1));
+        assert!(!strict.contains_key(&2));
+    }
+
+    #[test]
+    fn test_unicode_normalization() {
+        let decomposed = "e\u{301}le\u{300}ve";
+        assert_eq!(normalize_unicode(decomposed, UnicodeForm::Nfc), "élève");
+        assert_eq!(normalize_unicode("cou\u{AD}rir\u{200B}", UnicodeForm::Nfc), "courir");
+        assert_eq!(normalize_unicode("99\u{A0}€", UnicodeForm::Nfc), "99 €");
+        assert_eq!(normalize_unicode("\u{FB01}n", UnicodeForm::Nfkc), "fin");
+        assert_eq!(normalize_unicode("\u{FB01}n", UnicodeForm::Nfc), "\u{FB01}n");
+
+        assert_eq!(fold_diacritics("élève"), "eleve");
+        assert_eq!(fold_diacritics("cœur"), "coeur");
+        assert_eq!(fold_diacritics("Ça"), "Ca");
+    }
+
+    #[test]
+    fn test_fold_diacritics_merges_forms() {
+        let content = "l'élève et l'eleve. Un e\u{301}le\u{300}ve.";
+        let mut analyzer =
+            TextAnalyzer::new(content, "stop_words_french.txt").unwrap();
+        analyzer.set_fold_diacritics(true);
+        analyzer.normalize_unicode(UnicodeForm::Nfc);
+        analyzer.analyze();
+        analyzer.clean_word();
+        analyzer.count_words();
+        analyzer.word_frequency_ngrams(1);
+
+        let frequencies = analyzer.ngram_frequencies.get(&1).unwrap();
+        assert_eq!(frequencies.get("eleve"), Some(&3));
+        let forms = analyzer.get_surface_forms(1).unwrap().get("eleve").unwrap();
+        assert_eq!(forms.get("élève"), Some(&2));
+        assert_eq!(forms.get("eleve"), Some(&1));
+    }
+
+    #[test]
+    fn test_elision_rules() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from(
+            "Qu'il vienne jusqu'à Paris, lorsqu'on m'appelle. L’Éducation (l'école)\n\npuisqu'elle aujourd'hui",
+        );
+        analyzer.clean_word();
+        assert_eq!(
+            analyzer.content,
+            "il vienne à Paris, on appelle. Éducation (école)\n\nelle aujourd'hui"
+        );
+
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_english.txt").unwrap();
+        analyzer.content = String::from("We're sure you don't know. It can't, won't. The company's I've");
+        analyzer.clean_word();
+        assert_eq!(
+            analyzer.content,
+            "We are sure you do not know. It can not, will not. The company I have"
+        );
+    }
+
+    #[test]
+    fn test_concordance() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from(
+            "le professeur des écoles enseigne. Devenir professeur des écoles demande un concours.",
+        );
+        let keys = vec!["professeur".to_string(), "des".to_string(), "écoles".to_string()];
+        let lines = analyzer.concordance(&keys, 2);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].left, "le");
+        assert_eq!(lines[0].keyword, "professeur des écoles");
+        assert_eq!(lines[0].right, "enseigne. Devenir");
+        assert_eq!(lines[0].token_offset, 1);
+        assert_eq!(lines[1].left, "enseigne. Devenir");
+        assert_eq!(lines[1].right, "demande un");
+        assert_eq!(lines[1].token_offset, 6);
+
+        assert!(analyzer.concordance(&["absent".to_string()], 2).is_empty());
+
+        // Un contexte démesuré reprend le texte entier de part et d'autre
+        let lines = analyzer.concordance(&keys, usize::MAX);
+        assert_eq!(lines[1].left, "le professeur des écoles enseigne. Devenir");
+        assert_eq!(lines[1].right, "demande un concours");
+    }
+
+    #[actix_web::test]
+    async fn test_concordance_client_errors() {
+        use actix_web::{test, web, App};
+
+        let store = web::Data::new(crate::api::AnalysisStore::default());
+        let app = test::init_service(App::new().app_data(store).service(crate::api::concordance)).await;
+        let status = |body: serde_json::Value| {
+            let request = test::TestRequest::post().uri("/api/concordance").set_json(body).to_request();
+            test::call_service(&app, request)
+        };
+        let unknown = status(serde_json::json!({ "analysis_id": "inconnue", "expression": "concours" })).await;
+        assert_eq!(unknown.status(), 400);
+        let missing = status(serde_json::json!({ "expression": "concours" })).await;
+        assert_eq!(missing.status(), 400);
+        let empty = status(serde_json::json!({ "urls": [], "expression": " ... " })).await;
+        assert_eq!(empty.status(), 400);
+    }
+
+    #[test]
+    fn test_customize_stop_words() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from("accepter les cookies de la newsletter avant le bac 2024");
+        analyzer.set_drop_numeric_tokens(true);
+        analyzer.customize_stop_words(
+            &["Cookies".to_string(), "newsletter".to_string()],
+            &["avant".to_string()],
+            &["bac 2024".to_string(), "de la".to_string()],
+        );
+        analyzer.word_frequency_ngrams(1);
+        analyzer.word_frequency_ngrams(2);
+
+        let unigrams = analyzer.ngram_frequencies.get(&1).unwrap();
+        assert!(!unigrams.contains_key("cookies"));
+        assert!(!unigrams.contains_key("newsletter"));
+        assert_eq!(unigrams.get("avant"), Some(&1));
+        // Le nombre seul reste écarté, mais pas l'expression protégée
+        assert!(!unigrams.contains_key("2024"));
+        let bigrams = analyzer.ngram_frequencies.get(&2).unwrap();
+        assert_eq!(bigrams.get("bac 2024"), Some(&1));
+        assert_eq!(bigrams.get("de la"), Some(&1));
+
+        // La liste partagée n'est pas modifiée
+        let mut other = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        other.content = String::from("les cookies");
+        other.word_frequency_ngrams(1);
+        assert!(other.ngram_frequencies.get(&1).unwrap().contains_key("cookies"));
+    }
+
+    #[test]
+    fn test_analyzer_with_content() {
+        let mut template = TextAnalyzer::new("", "stop_words_french.txt").unwrap();
+        template.set_stemmer(Some(Stemmer::new(Language::French)));
+        template.customize_stop_words(&["newsletter".to_string()], &[], &[]);
+        template.analyze();
+
+        let mut analyzer = template.with_content("Les Professeurs et la newsletter des professeures");
+        analyzer.word_frequency_ngrams(1);
+        let unigrams = analyzer.ngram_frequencies.get(&1).unwrap();
+        assert_eq!(unigrams.get("professeur"), Some(&2));
+        assert!(!unigrams.contains_key("newsletter"));
+        assert_eq!(analyzer.count_words(), 7);
+
+        // Le modèle reste vide et réutilisable
+        let mut other = template.with_content("un professeur");
+        other.word_frequency_ngrams(1);
+        assert_eq!(other.ngram_frequencies.get(&1).unwrap().get("professeur"), Some(&1));
+        assert!(template.tokens().is_empty());
+
+        // Personnaliser un analyseur ne modifie pas la liste partagée
+        let mut customized = template.with_content("un professeur");
+        customized.customize_stop_words(&["professeur".to_string()], &[], &[]);
+        customized.word_frequency_ngrams(1);
+        assert!(!customized.ngram_frequencies.get(&1).unwrap().contains_key("professeur"));
+        let mut other = template.with_content("un professeur");
+        other.word_frequency_ngrams(1);
+        assert_eq!(other.ngram_frequencies.get(&1).unwrap().get("professeur"), Some(&1));
+    }
+
+    #[test]
+    fn test_entities() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from(
+            "prix 99,99€ ou $15, soit 100% remboursé le 12/03/2024 ou le 1er janvier 2025 : \
+             5 km, 16go et 20 °C pour 3 personnes",
+        );
+        let counts = analyzer.entity_counts();
+        assert_eq!(counts.get(&EntityKind::Price), Some(&2));
+        assert_eq!(counts.get(&EntityKind::Percentage), Some(&1));
+        assert_eq!(counts.get(&EntityKind::Date), Some(&2));
+        assert_eq!(counts.get(&EntityKind::Measurement), Some(&3));
+        assert_eq!(counts.get(&EntityKind::Number), Some(&1));
+        // Par défaut, les tokens ne sont pas modifiés
+        assert!(analyzer.tokens().iter().any(|token| token.text == "99,99"));
+
+        analyzer.set_entity_mode(EntityMode::Placeholder);
+        analyzer.word_frequency_ngrams(1);
+        let unigrams = analyzer.ngram_frequencies.get(&1).unwrap();
+        assert_eq!(unigrams.get("<PRICE>"), Some(&2));
+        assert_eq!(unigrams.get("<DATE>"), Some(&2));
+        assert!(!unigrams.contains_key("99,99"));
+        let price = analyzer.tokens().iter().find(|token| token.text == "<PRICE>").unwrap();
+        assert_eq!(price.form(), "99,99€");
+
+        analyzer.set_entity_mode(EntityMode::Exclude);
+        analyzer.word_frequency_ngrams(1);
+        analyzer.word_frequency_ngrams(2);
+        assert!(!analyzer.ngram_frequencies.get(&1).unwrap().contains_key("<PRICE>"));
+        assert!(analyzer.ngram_frequencies.get(&2).unwrap().keys().all(|key| !key.contains('<')));
+        assert!(analyzer.ngram_frequencies.get(&1).unwrap().contains_key("remboursé"));
+    }
+
+    #[test]
+    fn test_numeric_date_with_oversized_part() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from("réf. 2024/99999999999/12 et 12/99999999999/2024");
+        let counts = analyzer.entity_counts();
+        assert_eq!(counts.get(&EntityKind::Date), None);
+    }
+
+    #[test]
+    fn test_books_and_ages_are_plain_numbers() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from("J'ai lu 3 livres à 12 ans, puis payé 40 livres sterling.");
+        let counts = analyzer.entity_counts();
+        assert_eq!(counts.get(&EntityKind::Price), Some(&1));
+        assert_eq!(counts.get(&EntityKind::Measurement), None);
+        assert_eq!(counts.get(&EntityKind::Number), Some(&2));
+    }
+
+    #[test]
+    fn test_common_words_are_not_months() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_english.txt").unwrap();
+        analyzer.content = String::from("You may 2 times. The 4 mar the wall.");
+        let counts = analyzer.entity_counts();
+        assert_eq!(counts.get(&EntityKind::Date), None);
+        assert_eq!(counts.get(&EntityKind::Number), Some(&2));
+
+        // Avec une majuscule ou dans une date complète, ce sont des mois
+        analyzer.content = String::from("Since May 2, then 4 mar 2024 and may 3 2025. Ils étaient sept 3 fois.");
+        let counts = analyzer.entity_counts();
+        assert_eq!(counts.get(&EntityKind::Date), Some(&3));
+        assert_eq!(counts.get(&EntityKind::Number), Some(&1));
+    }
+
+    #[test]
+    fn test_pos_tagging() {
+        let tagger = PosTagger::load(Language::French).unwrap();
+        let tokens = UnicodeTokenizer
+            .tokenize("le professeur des écoles publiques est généralement occupé");
+        assert_eq!(
+            tagger.tag_tokens(&tokens),
+            vec![
+                PosTag::Det,
+                PosTag::Noun,
+                PosTag::Prep,
+                PosTag::Noun,
+                PosTag::Adj,
+                PosTag::Verb,
+                PosTag::Adv,
+                PosTag::Adj,
+            ]
+        );
+
+        let tagger = PosTagger::load(Language::English).unwrap();
+        let tokens = UnicodeTokenizer.tokenize("the teacher quickly graded 12 creative essays");
+        assert_eq!(
+            tagger.tag_tokens(&tokens),
+            vec![
+                PosTag::Det,
+                PosTag::Noun,
+                PosTag::Adv,
+                PosTag::Verb,
+                PosTag::Num,
+                PosTag::Adj,
+                PosTag::Noun,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pos_patterns() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from(
+            "le professeur des écoles prépare un concours national. Le professeur des écoles réussit.",
+        );
+        analyzer.set_pos_tagger(Some(PosTagger::load(Language::French).unwrap()));
+        let pattern = |text: &str| PosPattern::try_from(text.to_string()).unwrap();
+        analyzer.set_pos_patterns(HashMap::from([
+            (2, vec![pattern("NOUN ADJ")]),
+            (3, vec![pattern("NOUN PREP NOUN")]),
+        ]));
+        analyzer.word_frequency_ngrams(2);
+        analyzer.word_frequency_ngrams(3);
+
+        let bigrams: Vec<&String> = analyzer.ngram_frequencies.get(&2).unwrap().keys().collect();
+        assert_eq!(bigrams, vec!["concours national"]);
+        let trigrams = analyzer.ngram_frequencies.get(&3).unwrap();
+        assert_eq!(trigrams.len(), 1);
+        assert_eq!(trigrams.get("professeur des écoles"), Some(&2));
+
+        assert!(PosPattern::try_from("NOUN VERBE".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_case_preservation() {
+        let mut analyzer = TextAnalyzer::new("analyser.txt", "stop_words_french.txt").unwrap();
+        analyzer.content = String::from("Le CAPES se passe à Paris. Les candidats du capes visitent Paris.");
+        analyzer.analyze();
+        analyzer.word_frequency_ngrams(1);
+
+        // Le contenu garde sa casse, les expressions sont comptées en minuscules
+        assert!(analyzer.content.starts_with("Le CAPES"));
+        let unigrams = analyzer.ngram_frequencies.get(&1).unwrap();
+        assert_eq!(unigrams.get("paris"), Some(&2));
+        assert_eq!(unigrams.get("capes"), Some(&2));
+        assert!(!unigrams.contains_key("le"));
+        let forms = analyzer.get_surface_forms(1).unwrap();
+        assert_eq!(forms.get("paris").unwrap().get("Paris"), Some(&2));
+        assert_eq!(forms.get("capes").unwrap().get("CAPES"), Some(&1));
+        assert_eq!(forms.get("capes").unwrap().get("capes"), Some(&1));
+
+        let keyphrases = analyzer.rake_keyphrases();
+        let capes = keyphrases.iter().find(|k| k.phrase == "capes").unwrap();
+        assert_eq!(capes.surface_forms.get("CAPES"), Some(&1));
+    }
+
+    #[test]
+    fn test_inclusive_forms() {
+        let form = |word: &str| InclusiveForm::parse(word).unwrap();
+        for word in ["enseignant·e·s", "enseignant.e.s", "enseignant-e-s", "enseignant(e)s", "enseignant·es"] {
+            assert_eq!(form(word).canonical(), "enseignant·e·s");
+            assert_eq!(form(word).masculine(), "enseignants");
+            assert_eq!(form(word).feminine(), "enseignantes");
+        }
+        assert_eq!(form("professeur·e").feminine(), "professeure");
+        assert_eq!(form("acteur·rice·s").feminine(), "actrices");
+        assert_eq!(form("acteur(trice)").feminine(), "actrice");
+        assert_eq!(form("danseur·euse").feminine(), "danseuse");
+        assert_eq!(form("sportif·ve·s").feminine(), "sportives");
+        assert_eq!(form("heureux·se").masculine(), "heureux");
+        assert_eq!(form("heureux·se").feminine(), "heureuse");
+
+        for word in ["porte-monnaie", "site.es", "e.g", "c'est-à-dire", "exemple"] {
+            assert_eq!(InclusiveForm::parse(word), None);
+        }
+
+        // Impératifs suivis d'un pronom : le trait d'union n'annonce pas une forme inclusive
+        for word in ["Prends-le", "dis-le", "allez-y", "Prenez-la", "Donnez-les", "Rends-le-lui"] {
+            assert_eq!(InclusiveForm::parse(word), None, "{}", word);
+        }
+        let text = "Prends-le et dis-le, allez-y !";
+        assert_eq!(canonicalize_inclusive(text), text);
+        assert_eq!(form("citoyen-ne-s").feminine(), "citoyennes");
+        assert_eq!(form("professionnel-le").feminine(), "professionnelle");
+        assert_eq!(InclusiveForm::parse("auteur-ne"), None);
+
+        // Deux phrases collées ne forment pas une forme inclusive
+        for word in ["fin.Le", "fin.le", "prix.Les", "étudiant.E.s", "citoyen-Ne"] {
+            assert_eq!(InclusiveForm::parse(word), None, "{}", word);
+        }
+        assert_eq!(canonicalize_inclusive("la fin.Le début"), "la fin.Le début");
+        assert_eq!(form("citoyen.ne.s").feminine(), "citoyennes");
+    }
+
+    #[test]
+    fn test_inclusive_writing_modes() {
+        let content = "Les enseignant.e.s et étudiant(e)s. Des enseignant·e·s motivé·e·s.";
+        let mut analyzer = TextAnalyzer::new(content, "stop_words_french.txt").unwrap();
+        analyzer.normalize_inclusive_writing();
+        assert_eq!(
+            analyzer.content,
+            "Les enseignant·e·s et étudiant·e·s. Des enseignant·e·s motivé·e·s."
+        );
+        analyzer.word_frequency_ngrams(1);
+        assert_eq!(analyzer.ngram_frequencies.get(&1).unwrap().get("enseignant·e·s"), Some(&2));
+
+        let mut analyzer = TextAnalyzer::new(content, "stop_words_french.txt").unwrap();
+        analyzer.set_inclusive_mode(InclusiveMode::Expand);
+        analyzer.normalize_inclusive_writing();
+        analyzer.word_frequency_ngrams(1);
+        analyzer.word_frequency_ngrams(2);
+        let unigrams = analyzer.ngram_frequencies.get(&1).unwrap();
+        assert_eq!(unigrams.get("enseignants"), Some(&2));
+        assert_eq!(unigrams.get("enseignantes"), Some(&2));
+        assert_eq!(unigrams.get("étudiantes"), Some(&1));
+        let bigrams = analyzer.ngram_frequencies.get(&2).unwrap();
+        assert!(!bigrams.contains_key("enseignants enseignantes"));
+        assert_eq!(bigrams.get("enseignants motivés"), Some(&1));
+        assert_eq!(bigrams.get("enseignantes motivées"), Some(&1));
+        assert!(!bigrams.contains_key("enseignantes motivés"));
+        assert!(!bigrams.contains_key("enseignants motivées"));
+        // Chaque forme inclusive compte pour un seul mot
+        assert_eq!(analyzer.count_words(), 7);
+        let lines = analyzer.concordance(&["enseignantes".to_string(), "motivées".to_string()], 1);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].keyword, "enseignant·e·s motivé·e·s");
+
+        let mut analyzer = TextAnalyzer::new(content, "stop_words_french.txt").unwrap();
+        analyzer.set_inclusive_mode(InclusiveMode::Off);
+        analyzer.normalize_inclusive_writing();
+        assert_eq!(analyzer.content, content);
+    }
+
+    #[test]
+    fn test_inclusive_expand_keyphrases() {
+        let content = "Les enseignant·e·s motivé·e·s enseignent.";
+        let expanded = || {
+            let mut analyzer = TextAnalyzer::new(content, "stop_words_french.txt").unwrap();
+            analyzer.set_inclusive_mode(InclusiveMode::Expand);
+            analyzer.analyze();
+            analyzer
+        };
+
+        let rake: Vec<String> = expanded().rake_keyphrases().into_iter().map(|k| k.phrase).collect();
+        assert!(rake.contains(&"enseignants motivés enseignent".to_string()));
+        assert!(rake.contains(&"enseignantes motivées enseignent".to_string()));
+
+        let textrank = expanded().textrank_keyphrases();
+        assert!(textrank.iter().any(|keyphrase| keyphrase.phrase.contains("enseignantes")));
+        // Les mots sans forme inclusive ne sont pas comptés deux fois
+        let enseignent = textrank.iter().find(|keyphrase| keyphrase.phrase == "enseignent").unwrap();
+        assert_eq!(enseignent.occurrences, 1);
+
+        assert_eq!(
+            expanded().content_words(),
+            [["enseignants", "motivés", "enseignent"], ["enseignantes", "motivées", "enseignent"]]
+        );
+    }
+
+    #[test]
+    fn test_main_content_extraction() {
+        let html = r#"<html lang="fr"><head><title>Devenir professeur</title></head><body>
+            <div id="cookie-banner">Nous utilisons des cookies. Accepter les cookies et continuer la navigation.</div>
+            <header><nav><ul>
+                <li><a href="/">Accueil</a></li><li><a href="/concours">Concours</a></li><li><a href="/contact">Contact</a></li>
+            </ul></nav></header>
+            <div class="layout">
+                <div class="article-content">
+                    <h1>Le concours de professeur des écoles</h1>
+                    <p>Le concours de recrutement de professeur des écoles, ouvert chaque année, comporte des épreuves écrites et orales.</p>
+                    <p>Les candidats doivent préparer le français, les mathématiques, et une épreuve d'entretien avec le jury.</p>
+                    <p>La préparation dure souvent plusieurs mois, avec des annales, des oraux blancs et des stages.</p>
+                </div>
+                <aside class="sidebar"><p>Articles liés : <a href="/a">les salaires des enseignants en début de carrière</a></p></aside>
+            </div>
+            <footer><p>Mentions légales, politique de confidentialité et plan du site de notre association.</p></footer>
+            <script>var tracking = "ne pas compter";</script>
+        </body></html>"#;
+
+        let analyzer = WebAnalyzer::new("https://example.com");
+        let page = analyzer.analyze_html(html);
+        assert_eq!(page.lang.as_deref(), Some("fr"));
+        assert!(page.content.starts_with("Devenir professeur"));
+        assert!(page.content.contains("Le concours de professeur des écoles"));
+        assert!(page.content.contains("oraux blancs et des stages."));
+        for boilerplate in ["cookies", "Accueil", "salaires", "Mentions légales", "tracking"] {
+            assert!(!page.content.contains(boilerplate), "{} ne devrait pas être extrait", boilerplate);
+        }
+
+        let mut analyzer = WebAnalyzer::new("https://example.com");
+        analyzer.set_extraction(ContentExtraction::FullBody);
+        let page = analyzer.analyze_html(html);
+        for text in ["Accepter les cookies", "Accueil", "salaires", "Mentions légales", "oraux blancs"] {
+            assert!(page.content.contains(text), "{} devrait être extrait", text);
+        }
+        assert!(!page.content.contains("tracking"));
+
+        // Sans paragraphe exploitable, tout le corps de la page est repris
+        let page = WebAnalyzer::new("https://example.com").analyze_html("<html><body><span>Bonjour</span></body></html>");
+        assert!(page.content.contains("Bonjour"));
+    }
+
+    #[test]
+    fn test_page_zones() {
+        let html = r#"<html><head>
+            <title>Concours de professeur</title>
+            <meta name="description" content="Tout savoir sur le concours de professeur des écoles">
+            <meta name="keywords" content="concours,professeur">
+        </head><body><article>
+            <h1>Réussir le concours de professeur des écoles</h1>
+            <p>Le concours de professeur des écoles comporte des épreuves écrites, puis des épreuves orales.</p>
+            <h2>Les épreuves orales</h2>
+            <p>L'oral du concours, très sélectif, se prépare avec le <a href="/guide">guide du jury</a> et des oraux blancs.</p>
+            <img src="jury.jpg" alt="Jury du concours">
+        </article></body></html>"#;
+
+        let page = WebAnalyzer::new("https://example.com").analyze_html(html);
+        assert_eq!(page.zones.get(&Zone::Title).map(String::as_str), Some("Concours de professeur"));
+        assert_eq!(
+            page.zones.get(&Zone::Headings).map(String::as_str),
+            Some("Réussir le concours de professeur des écoles . Les épreuves orales")
+        );
+        assert_eq!(page.zones.get(&Zone::Anchors).map(String::as_str), Some("guide du jury"));
+        assert_eq!(page.zones.get(&Zone::AltText).map(String::as_str), Some("Jury du concours ."));
+        let body = page.zones.get(&Zone::Body).unwrap();
+        assert!(body.contains("se prépare avec le . et des oraux blancs."));
+        assert!(!body.contains("Réussir"));
+        assert!(page.zones.get(&Zone::Meta).unwrap().contains("concours, professeur"));
+
+        let mut analyzer = TextAnalyzer::new(&page.content, "stop_words_french.txt").unwrap();
+        for (zone, text) in &page.zones {
+            let mut zone_analyzer = TextAnalyzer::new(text, "stop_words_french.txt").unwrap();
+            zone_analyzer.analyze();
+            analyzer.add_zone(*zone, zone_analyzer);
+        }
+        let unigrams = analyzer.zone_frequencies(1);
+        let concours = unigrams.get("concours").unwrap();
+        assert_eq!(concours.get(&Zone::Title), Some(&1));
+        assert_eq!(concours.get(&Zone::Meta), Some(&2));
+        assert_eq!(concours.get(&Zone::Headings), Some(&1));
+        assert_eq!(concours.get(&Zone::Body), Some(&2));
+        assert_eq!(concours.get(&Zone::AltText), Some(&1));
+        assert_eq!(concours.get(&Zone::Anchors), None);
+        // Les fragments d'une zone ne forment pas de n-gramme entre eux
+        assert!(!analyzer.zone_frequencies(2).contains_key("écoles épreuves"));
+
+        let weights = ZoneWeights::default();
+        assert_eq!(weights.score(concours), 3.0 + 2.0 * 1.5 + 2.0 + 2.0 + 0.5);
+        let weights = ZoneWeights::new(HashMap::from([(Zone::Title, 10.0), (Zone::Meta, 0.0)]));
+        assert_eq!(weights.score(concours), 10.0 + 2.0 + 2.0 + 0.5);
+    }
+
+    #[test]
+    fn test_zone_fragments_stay_apart() {
+        let html = r#"<html><body><article>
+            <h2>Concours</h2><h2>Épreuves</h2>
+            <p>Le calendrier des concours et des épreuves est publié chaque année.</p>
+            <p><a href="/inscription">Inscription</a> <a href="/resultats">Résultats</a></p>
+        </article></body></html>"#;
+
+        let page = WebAnalyzer::new("https://example.com").analyze_html(html);
+        let mut analyzer = TextAnalyzer::new(&page.content, "stop_words_french.txt").unwrap();
+        for (zone, text) in &page.zones {
+            let mut zone_analyzer = TextAnalyzer::new(text, "stop_words_french.txt").unwrap();
+            zone_analyzer.analyze();
+            analyzer.add_zone(*zone, zone_analyzer);
+        }
+        let bigrams = analyzer.zone_frequencies(2);
+        assert!(!bigrams.contains_key("concours épreuves"));
+        assert!(!bigrams.contains_key("inscription résultats"));
+        assert_eq!(analyzer.zone_frequencies(1).get("épreuves").and_then(|counts| counts.get(&Zone::Headings)), Some(&1));
+
+        // Sans limite de phrase, le séparateur seul ne suffit pas
+        let mut headings = TextAnalyzer::new(&page.zones[&Zone::Headings], "stop_words_french.txt").unwrap();
+        headings.analyze();
+        headings.word_frequency_ngrams(2);
+        assert_eq!(headings.ngram_frequencies.get(&2).unwrap().get("concours épreuves"), Some(&1));
+    }
+
+    #[test]
+    fn test_heading_outline() {
+        let html = r#"<html><body>
+            <header><h1>Devenir professeur des écoles</h1></header>
+            <nav><h2>Menu</h2></nav>
+            <article>
+                <h2>Les épreuves  écrites</h2>
+                <h3>Français</h3>
+                <h3>Mathématiques</h3>
+                <h2>Les épreuves orales</h2>
+                <h4>Entretien</h4>
+                <h2> </h2>
+            </article>
+            <footer><h3>Newsletter</h3></footer>
+        </body></html>"#;
+
+        let heading = |level, text: &str, children| Heading { level, text: text.to_string(), children };
+        let page = WebAnalyzer::new("https://example.com").analyze_html(html);
+        assert_eq!(
+            page.outline,
+            vec![heading(
+                1,
+                "Devenir professeur des écoles",
+                vec![
+                    heading(2, "Les épreuves écrites", vec![
+                        heading(3, "Français", vec![]),
+                        heading(3, "Mathématiques", vec![]),
+                    ]),
+                    heading(2, "Les épreuves orales", vec![heading(4, "Entretien", vec![])]),
+                ],
+            )]
+        );
+        let flattened: Vec<&str> = page.outline[0].flatten().iter().map(|heading| heading.text.as_str()).collect();
+        assert_eq!(flattened[..3], ["Devenir professeur des écoles", "Les épreuves écrites", "Français"]);
+        // La zone des intertitres suit le plan : l'en-tête compte, la navigation et le pied de page non
+        let headings = page.zones.get(&Zone::Headings).unwrap();
+        assert!(headings.starts_with("Devenir professeur des écoles . Les épreuves écrites"));
+        assert!(!headings.contains("Menu") && !headings.contains("Newsletter"));
+
+        let mut analyzer = WebAnalyzer::new("https://example.com");
+        analyzer.set_extraction(ContentExtraction::FullBody);
+        let page = analyzer.analyze_html(html);
+        let flattened: Vec<&str> = page.outline.iter().flat_map(Heading::flatten).map(|heading| heading.text.as_str()).collect();
+        assert!(flattened.contains(&"Menu") && flattened.contains(&"Newsletter"));
+
+        // Le thème d'un titre est formé de ses mots pleins
+        let mut analyzer = TextAnalyzer::new("Les Épreuves orales du concours", "stop_words_french.txt").unwrap();
+        analyzer.analyze();
+        assert_eq!(analyzer.content_words(), [["épreuves", "orales", "concours"]]);
+    }
+
+    #[test]
+    fn test_structured_data() {
+        let html = r#"<html><head>
+            <title>Préparer le CRPE</title>
+            <meta property="og:title" content="Préparer le CRPE en 6 mois">
+            <meta property="og:type" content="article">
+            <meta property="og:image" content="https://example.com/a.jpg">
+            <meta property="og:image" content="https://example.com/b.jpg">
+            <meta name="twitter:card" content="summary_large_image">
+            <meta name="twitter:site" content="@example">
+            <script type="application/ld+json">
+                {"@context": "https://schema.org", "@graph": [
+                    {"@type": "Article", "headline": "Préparer le CRPE", "author": {"@type": "Person", "name": "Alice"}},
+                    {"@type": ["Course", "Product"], "name": "Formation CRPE"}
+                ]}
+            </script>
+            <script type="application/ld+json"><!-- [{"@type": "FAQPage"}] --></script>
+            <script type="application/ld+json">{ invalide </script>
+        </head><body>
+            <div itemscope itemtype="https://schema.org/Event">
+                <span itemprop="name">Journée portes ouvertes</span>
+                <time itemprop="startDate" datetime="2024-03-16">16 mars</time>
+                <div itemprop="location" itemscope itemtype="https://schema.org/Place">
+                    <span itemprop="name">INSPÉ de Lyon</span>
+                </div>
+            </div>
+        </body></html>"#;
+
+        let metadata = WebAnalyzer::new("https://example.com").analyze_html(html).metadata;
+        assert_eq!(metadata.title, "Préparer le CRPE");
+        assert_eq!(metadata.open_graph.title.as_deref(), Some("Préparer le CRPE en 6 mois"));
+        assert_eq!(metadata.open_graph.kind.as_deref(), Some("article"));
+        assert_eq!(metadata.open_graph.image.as_deref(), Some("https://example.com/a.jpg"));
+        assert_eq!(metadata.twitter_card.card.as_deref(), Some("summary_large_image"));
+        assert_eq!(metadata.twitter_card.site.as_deref(), Some("@example"));
+        assert_eq!(metadata.json_ld.len(), 2);
+
+        let event = &metadata.microdata[0];
+        assert_eq!(metadata.microdata.len(), 1);
+        assert_eq!(event.types, ["https://schema.org/Event"]);
+        assert_eq!(event.properties["name"], [MicrodataValue::Text("Journée portes ouvertes".to_string())]);
+        assert_eq!(event.properties["startDate"], [MicrodataValue::Text("2024-03-16".to_string())]);
+        let MicrodataValue::Item(place) = &event.properties["location"][0] else { panic!("item attendu") };
+        assert_eq!(place.properties["name"], [MicrodataValue::Text("INSPÉ de Lyon".to_string())]);
+
+        assert_eq!(
+            metadata.schema_types,
+            ["Article", "Course", "Event", "FAQPage", "Person", "Place", "Product"]
+        );
+    }
+
+    #[test]
+    fn test_link_inventory() {
+        let html = r#"<html><head><base href="/concours/"></head><body>
+            <nav><a href="/">Accueil</a> <a href="https://www.example.com/contact">Contact</a></nav>
+            <article><p>Consultez <a href="epreuves.html">les épreuves du  concours</a>,
+                le <a href="https://education.gouv.fr/crpe" rel="nofollow noopener">site officiel</a>
+                et notre <a href="https://partenaire.fr/formation" rel="Sponsored">formation partenaire</a>.</p>
+                <a href="https://forum.example.org/sujet" rel="ugc nofollow"><img src="f.png" alt="Forum des candidats"></a>
+                <a href="mailto:contact@example.com">Écrire</a>
+                <a href="javascript:void(0)">Ouvrir</a>
+            </article>
+        </body></html>"#;
+
+        let page = WebAnalyzer::new("https://example.com/guide/crpe").analyze_html(html);
+        let links: Vec<(&str, &str, LinkKind)> = page
+            .links
+            .iter()
+            .map(|link| (link.url.as_str(), link.anchor_text.as_str(), link.kind))
+            .collect();
+        assert_eq!(
+            links,
+            [
+                ("https://example.com/", "Accueil", LinkKind::Internal),
+                ("https://www.example.com/contact", "Contact", LinkKind::Internal),
+                ("https://example.com/concours/epreuves.html", "les épreuves du concours", LinkKind::Internal),
+                ("https://education.gouv.fr/crpe", "site officiel", LinkKind::External),
+                ("https://partenaire.fr/formation", "formation partenaire", LinkKind::External),
+                ("https://forum.example.org/sujet", "Forum des candidats", LinkKind::External),
+            ]
+        );
+        assert_eq!(page.links[3].rel, ["nofollow", "noopener"]);
+        assert_eq!(page.links[4].rel, ["sponsored"]);
+        assert_eq!(
+            LinkStats::from_links(&page.links),
+            LinkStats { total: 6, internal: 3, external: 3, nofollow: 2, sponsored: 1, ugc: 1 }
+        );
+
+        // Le texte des liens forme sa propre zone, navigation comprise
+        assert_eq!(
+            page.zones.get(&Zone::Anchors).map(String::as_str),
+            Some("Accueil . Contact . les épreuves du concours . site officiel . formation partenaire . Forum des candidats")
+        );
+        let mut analyzer = TextAnalyzer::new(&page.content, "stop_words_french.txt").unwrap();
+        let mut anchors = TextAnalyzer::new(&page.zones[&Zone::Anchors], "stop_words_french.txt").unwrap();
+        anchors.analyze();
+        analyzer.add_zone(Zone::Anchors, anchors);
+        let bigrams = analyzer.zone_frequencies(2);
+        assert_eq!(bigrams["site officiel"].get(&Zone::Anchors), Some(&1));
+        assert!(!bigrams.contains_key("accueil contact"));
+        assert!(!bigrams.contains_key("officiel formation"));
+    }
 }
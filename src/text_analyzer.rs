@@ -1,25 +1,119 @@
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fs;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::collocation::Association;
+use crate::elision::{elisions_path, ElisionRules};
+use crate::entity::{detect_entities, is_number, Entity, EntityKind, EntityMode};
+use crate::inclusive::{canonicalize_inclusive, InclusiveForm, InclusiveMode};
+use crate::language::Language;
+use crate::normalizer::{fold_diacritics, normalize_unicode, UnicodeForm};
+use crate::pos::{PosPattern, PosTag, PosTagger};
+use crate::readability::{count_syllables, Readability};
+use crate::sentence::segment_sentences;
+use crate::stemmer::Stemmer;
+use crate::tokenizer::{Token, Tokenizer, UnicodeTokenizer, WhitespaceTokenizer};
+use crate::zone::{Zone, ZoneCounts};
+
+// Expression normalisée -> (forme d'origine -> occurrences)
+pub type SurfaceForms = HashMap<String, HashMap<String, usize>>;
+
+// Occurrences et pourcentages d'une taille de n-gramme
+pub type NgramFrequency<'a> = (&'a HashMap<String, usize>, &'a HashMap<String, f64>);
+
+// Longueur maximale d'une expression candidate de RAKE
+const RAKE_MAX_PHRASE_WORDS: usize = 5;
+
+// Paramètres de TextRank : fenêtre de co-occurrence, amortissement et convergence
+const TEXTRANK_WINDOW: usize = 4;
+const TEXTRANK_DAMPING: f64 = 0.85;
+const TEXTRANK_MAX_ITERATIONS: usize = 100;
+const TEXTRANK_TOLERANCE: f64 = 1e-4;
+
+/// Rule deciding which stop words an n-gram may contain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum StopWordPolicy {
+    /// Only the first and last words must not be stop words.
+    #[default]
+    Boundary,
+    /// No word of the n-gram may be a stop word.
+    NoStopWords,
+    /// Boundary rule, plus at most `max_ratio` of the words may be stop words.
+    MaxRatio { max_ratio: f64 },
+}
+
+/// Keyphrase of variable length extracted from the content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyphrase {
+    pub phrase: String,
+    pub score: f64,
+    pub occurrences: usize,
+    // Formes écrites de l'expression et leurs occurrences
+    pub surface_forms: HashMap<String, usize>,
+}
+
+/// Occurrence of an expression with the text of the tokens around it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConcordanceLine {
+    pub left: String,
+    pub keyword: String,
+    pub right: String,
+    // Position du premier mot de l'expression dans le flux de tokens
+    pub token_offset: usize,
+}
 
 #[derive(Debug)]
 pub struct TextAnalyzer {
     pub content: String,
     word_count: usize,
-    word_frequency: HashMap<String, usize>,
-    word_frequency_percentage: HashMap<String, f64>,
-    pub word_frequency_twograms: HashMap<String, usize>,
-    pub word_frequency_twograms_percentage: HashMap<String, f64>,
-    pub word_frequency_trigrams: HashMap<String, usize>,
-    pub word_frequency_trigrams_percentage: HashMap<String, f64>,
-    pub word_frequency_fourgrams: HashMap<String, usize>,
-    pub word_frequency_fourgrams_percentage: HashMap<String, f64>,
-    pub word_frequency_fivegrams: HashMap<String, usize>,
-    pub word_frequency_fivegrams_percentage: HashMap<String, f64>,
+    // n -> (expression -> occurrences) et n -> (expression -> pourcentage)
+    pub ngram_frequencies: HashMap<usize, HashMap<String, usize>>,
+    pub ngram_percentages: HashMap<usize, HashMap<String, f64>>,
+    // n -> formes fusionnées sous chaque expression normalisée
+    ngram_forms: HashMap<usize, SurfaceForms>,
     average_word_length: f64,
+    #[allow(dead_code)]
     longest_sentences: Vec<String>,
+    #[allow(dead_code)]
     punctuation_stats: HashMap<char, usize>,
-    ban_list: HashSet<String>,
+    // Tables en lecture seule, partagées entre les analyseurs créés par `with_content`
+    ban_list: Arc<HashSet<String>>,
+    // Expressions toujours retenues, même composées de mots vides ou de nombres
+    protected_terms: HashSet<String>,
+    elision_rules: Arc<ElisionRules>,
+    stop_word_policy: StopWordPolicy,
+    // Rejette les n-grammes contenant un nombre seul (« 2023 », « 99,99 »)
+    drop_numeric_tokens: bool,
+    // Traitement des nombres, prix, dates, pourcentages et mesures
+    entity_mode: EntityMode,
+    entities: Vec<Entity>,
+    tokenizer: Arc<dyn Tokenizer>,
+    stemmer: Option<Stemmer>,
+    // Étiquettes morphosyntaxiques des tokens et motifs autorisés par taille de n-gramme
+    pos_tagger: Option<Arc<PosTagger>>,
+    pos_tags: Vec<PosTag>,
+    pos_patterns: HashMap<usize, Vec<PosPattern>>,
+    // Compte « élève » et « eleve » comme une seule expression
+    fold_diacritics: bool,
+    // Écriture inclusive : forme unique ou forme masculine et féminine
+    inclusive_mode: InclusiveMode,
+    // Compte « Paris » et « paris » ensemble, la casse d'origine restant dans `form`
+    case_fold: bool,
+    tokens: Vec<Token>,
+    // Mode « expand » : le flux de tokens au féminin, position par position
+    feminine_tokens: Vec<Token>,
+    // Phrases du contenu, en positions d'octets
+    sentences: Vec<Range<usize>>,
+    // Contenu à partir duquel `tokens` et `sentences` ont été calculés
+    tokenized_content: Option<String>,
+    // Texte de chaque zone de la page, analysé avec les mêmes réglages
+    zones: Vec<(Zone, TextAnalyzer)>,
+    // Les n-grammes ne franchissent pas les fins de phrase (fragments d'une zone)
+    sentence_bounded: bool,
     // Statistiques pour chaque type de n-gramme
     pub retained_expressions: HashMap<usize, usize>, // n -> nombre après filtrage
     pub unique_expressions: HashMap<usize, usize>,   // n -> nombre avant filtrage
@@ -32,31 +126,425 @@ impl TextAnalyzer {
             .lines()
             .map(|line| line.to_string())
             .collect();
+        // Les règles d'élision sont facultatives pour une liste de mots vides personnalisée
+        let elisions_path = elisions_path(stop_words_path);
+        let has_elisions = elisions_path != stop_words_path && fs::metadata(&elisions_path).is_ok();
+        let elision_rules = if has_elisions {
+            ElisionRules::load(&elisions_path)?
+        } else {
+            ElisionRules::default()
+        };
 
         Ok(TextAnalyzer {
             content: content.to_string(),
             word_count: 0,
-            word_frequency: HashMap::new(),
-            word_frequency_percentage: HashMap::new(),
-            word_frequency_twograms: HashMap::new(),
-            word_frequency_twograms_percentage: HashMap::new(),
-            word_frequency_trigrams: HashMap::new(),
-            word_frequency_trigrams_percentage: HashMap::new(),
-            word_frequency_fourgrams: HashMap::new(),
-            word_frequency_fourgrams_percentage: HashMap::new(),
-            word_frequency_fivegrams: HashMap::new(),
-            word_frequency_fivegrams_percentage: HashMap::new(),
+            ngram_frequencies: HashMap::new(),
+            ngram_percentages: HashMap::new(),
+            ngram_forms: HashMap::new(),
             average_word_length: 0.0,
             longest_sentences: vec![],
             punctuation_stats: HashMap::new(),
-            ban_list,
+            ban_list: Arc::new(ban_list),
+            protected_terms: HashSet::new(),
+            elision_rules: Arc::new(elision_rules),
+            stop_word_policy: StopWordPolicy::default(),
+            drop_numeric_tokens: false,
+            entity_mode: EntityMode::default(),
+            entities: Vec::new(),
+            tokenizer: Arc::new(UnicodeTokenizer),
+            stemmer: None,
+            pos_tagger: None,
+            pos_tags: Vec::new(),
+            pos_patterns: HashMap::new(),
+            fold_diacritics: false,
+            case_fold: false,
+            inclusive_mode: InclusiveMode::default(),
+            tokens: Vec::new(),
+            feminine_tokens: Vec::new(),
+            sentences: Vec::new(),
+            tokenized_content: None,
+            zones: Vec::new(),
+            sentence_bounded: false,
             retained_expressions: HashMap::new(),
             unique_expressions: HashMap::new(),
         })
     }
 
+    /// Creates an analyzer of another content with the same settings. The stop
+    /// words, elision rules and lexicon are shared, not read again nor copied.
+    pub fn with_content(&self, content: &str) -> Self {
+        TextAnalyzer {
+            content: content.to_string(),
+            word_count: 0,
+            ngram_frequencies: HashMap::new(),
+            ngram_percentages: HashMap::new(),
+            ngram_forms: HashMap::new(),
+            average_word_length: 0.0,
+            longest_sentences: vec![],
+            punctuation_stats: HashMap::new(),
+            ban_list: Arc::clone(&self.ban_list),
+            protected_terms: self.protected_terms.clone(),
+            elision_rules: Arc::clone(&self.elision_rules),
+            stop_word_policy: self.stop_word_policy,
+            drop_numeric_tokens: self.drop_numeric_tokens,
+            entity_mode: self.entity_mode,
+            entities: Vec::new(),
+            tokenizer: Arc::clone(&self.tokenizer),
+            stemmer: self.stemmer.clone(),
+            pos_tagger: self.pos_tagger.clone(),
+            pos_tags: Vec::new(),
+            pos_patterns: self.pos_patterns.clone(),
+            fold_diacritics: self.fold_diacritics,
+            case_fold: self.case_fold,
+            inclusive_mode: self.inclusive_mode,
+            tokens: Vec::new(),
+            feminine_tokens: Vec::new(),
+            sentences: Vec::new(),
+            tokenized_content: None,
+            zones: Vec::new(),
+            sentence_bounded: self.sentence_bounded,
+            retained_expressions: HashMap::new(),
+            unique_expressions: HashMap::new(),
+        }
+    }
+
+    /// Replaces the tokenizer used by every counting function.
+    pub fn set_tokenizer(&mut self, tokenizer: Box<dyn Tokenizer>) {
+        self.tokenizer = tokenizer.into();
+        self.tokenized_content = None;
+    }
+
+    /// Enables or disables stemming of the tokens before counting.
+    pub fn set_stemmer(&mut self, stemmer: Option<Stemmer>) {
+        self.stemmer = stemmer;
+        self.tokenized_content = None;
+    }
+
+    /// Enables or disables part-of-speech tagging of the tokens.
+    pub fn set_pos_tagger(&mut self, tagger: Option<PosTagger>) {
+        self.pos_tagger = tagger.map(Arc::new);
+        self.tokenized_content = None;
+    }
+
+    /// Restricts the n-grams of each size to the given part-of-speech
+    /// patterns. Sizes without patterns are not restricted, and patterns
+    /// only apply once a tagger is set.
+    pub fn set_pos_patterns(&mut self, patterns: HashMap<usize, Vec<PosPattern>>) {
+        self.pos_patterns = patterns;
+    }
+
+    /// Enables or disables diacritic folding of the tokens before counting.
+    ///
+    /// Stop words are still matched on the accented form.
+    pub fn set_fold_diacritics(&mut self, fold: bool) {
+        self.fold_diacritics = fold;
+        self.tokenized_content = None;
+    }
+
+    /// Adjusts the stop-word list of this analyzer only.
+    ///
+    /// `extra` words are added to the list and `removed` words are taken out.
+    /// Protected terms are never treated as stop words: an n-gram equal to a
+    /// protected term is always kept, whatever the stop-word policy and the
+    /// numeric token filter. Words are compared in lowercase.
+    pub fn customize_stop_words(
+        &mut self,
+        extra: &[String],
+        removed: &[String],
+        protected: &[String],
+    ) {
+        let normalize = |word: &String| word.trim().to_lowercase();
+        // La liste partagée est copiée avant d'être modifiée
+        let ban_list = Arc::make_mut(&mut self.ban_list);
+        ban_list.extend(extra.iter().map(normalize));
+        for word in removed.iter().chain(protected).map(normalize) {
+            ban_list.remove(&word);
+        }
+        self.protected_terms = protected
+            .iter()
+            .map(|term| term.split_whitespace().collect::<Vec<&str>>().join(" ").to_lowercase())
+            .collect();
+    }
+
+    pub fn set_stop_word_policy(&mut self, policy: StopWordPolicy) {
+        self.stop_word_policy = policy;
+    }
+
+    pub fn set_drop_numeric_tokens(&mut self, drop: bool) {
+        self.drop_numeric_tokens = drop;
+    }
+
+    /// Chooses how gender-inclusive forms ("enseignant·e·s") are counted.
+    pub fn set_inclusive_mode(&mut self, mode: InclusiveMode) {
+        self.inclusive_mode = mode;
+        self.tokenized_content = None;
+    }
+
+    /// Chooses how typed entities (prices, dates...) are counted.
+    pub fn set_entity_mode(&mut self, mode: EntityMode) {
+        self.entity_mode = mode;
+        self.tokenized_content = None;
+    }
+
+    /// Number of typed entities of each kind in the content.
+    pub fn entity_counts(&mut self) -> HashMap<EntityKind, usize> {
+        self.refresh_tokens();
+        let mut counts = HashMap::new();
+        for entity in &self.entities {
+            *counts.entry(entity.kind).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Returns the token stream of the current content.
+    ///
+    /// Tokens are computed once and reused until `content` changes.
+    pub fn tokens(&mut self) -> &[Token] {
+        self.refresh_tokens();
+        &self.tokens
+    }
+
+    /// Returns the keys of the tokens that are not stop words, in order.
+    ///
+    /// In expand mode, the words of the feminine stream follow as a second
+    /// list when an inclusive form changes them.
+    pub fn content_words(&mut self) -> Vec<Vec<String>> {
+        self.refresh_tokens();
+        let mut streams = Vec::new();
+        for stream in [&self.tokens, &self.feminine_tokens] {
+            let words: Vec<String> = stream
+                .iter()
+                .filter(|token| !self.is_stop_token(token))
+                .map(|token| token.text.clone())
+                .collect();
+            if !words.is_empty() && !streams.contains(&words) {
+                streams.push(words);
+            }
+        }
+        streams
+    }
+
+    /// Returns the byte spans of the sentences of the current content.
+    ///
+    /// Sentences are segmented on the content as it is, punctuation included,
+    /// and refreshed together with the tokens.
+    pub fn sentences(&mut self) -> &[Range<usize>] {
+        self.refresh_tokens();
+        &self.sentences
+    }
+
+    fn refresh_tokens(&mut self) {
+        if self.tokenized_content.as_deref() != Some(self.content.as_str()) {
+            self.sentences = segment_sentences(&self.content);
+            self.tokens = self.tokenizer.tokenize(&self.content);
+            self.feminine_tokens.clear();
+            self.entities = detect_entities(&self.content, &self.tokens);
+            if self.entity_mode != EntityMode::Keep {
+                self.merge_entities();
+            }
+            if self.inclusive_mode == InclusiveMode::Expand {
+                self.expand_inclusive_forms();
+            }
+            if self.case_fold {
+                let tokens = self.tokens.iter_mut().chain(&mut self.feminine_tokens);
+                for token in tokens.filter(|token| token.entity.is_none()) {
+                    let lower = token.text.to_lowercase();
+                    if lower != token.text {
+                        let original = std::mem::replace(&mut token.text, lower);
+                        token.form.get_or_insert(original);
+                    }
+                }
+            }
+            if let Some(stemmer) = &self.stemmer {
+                let tokens = self.tokens.iter_mut().chain(&mut self.feminine_tokens);
+                for token in tokens.filter(|token| token.entity.is_none()) {
+                    let stem = stemmer.stem(&token.text);
+                    let original = std::mem::replace(&mut token.text, stem);
+                    token.form.get_or_insert(original);
+                }
+            }
+            // Le raciniseur s'appuie sur les accents : ils sont retirés après
+            if self.fold_diacritics {
+                let tokens = self.tokens.iter_mut().chain(&mut self.feminine_tokens);
+                for token in tokens.filter(|token| token.entity.is_none()) {
+                    let folded = fold_diacritics(&token.text);
+                    let original = std::mem::replace(&mut token.text, folded);
+                    token.form.get_or_insert(original);
+                }
+            }
+            self.pos_tags = self
+                .pos_tagger
+                .as_ref()
+                .map_or_else(Vec::new, |tagger| tagger.tag_tokens(&self.tokens));
+            self.tokenized_content = Some(self.content.clone());
+        }
+    }
+
+    // Remplace les tokens de chaque entité par un seul token typé
+    fn merge_entities(&mut self) {
+        let mut tokens = Vec::with_capacity(self.tokens.len());
+        let mut entities = self.entities.iter().peekable();
+        let mut i = 0;
+        while i < self.tokens.len() {
+            match entities.next_if(|entity| entity.tokens.start == i) {
+                Some(entity) => {
+                    tokens.push(Token {
+                        text: entity.kind.placeholder().to_string(),
+                        start: entity.start,
+                        end: entity.end,
+                        form: Some(self.content[entity.start..entity.end].to_string()),
+                        entity: Some(entity.kind),
+                    });
+                    i = entity.tokens.end;
+                }
+                None => {
+                    tokens.push(self.tokens[i].clone());
+                    i += 1;
+                }
+            }
+        }
+        self.tokens = tokens;
+    }
+
+    // Remplace chaque forme inclusive par sa forme masculine, et garde un
+    // second flux où elle est remplacée par sa forme féminine
+    fn expand_inclusive_forms(&mut self) {
+        let mut feminine_tokens = self.tokens.clone();
+        let mut expanded = false;
+        for (token, feminine) in self.tokens.iter_mut().zip(&mut feminine_tokens) {
+            let Some(inclusive) = InclusiveForm::parse(&token.text).filter(|_| token.entity.is_none()) else {
+                continue;
+            };
+            feminine.text = inclusive.feminine();
+            feminine.form = Some(token.text.clone());
+            token.form = Some(std::mem::replace(&mut token.text, inclusive.masculine()));
+            expanded = true;
+        }
+        if expanded {
+            self.feminine_tokens = feminine_tokens;
+        }
+    }
+
+    // Fenêtres de `n` tokens avec leur position : celles du flux masculin, puis
+    // celles du flux féminin qui en diffèrent, pour que chaque n-gramme reste
+    // d'un seul genre et que les mots sans forme inclusive ne comptent qu'une fois
+    fn token_windows(&self, n: usize) -> impl Iterator<Item = (usize, &[Token])> {
+        let feminine = self.feminine_tokens.windows(n).enumerate().filter(move |(offset, window)| {
+            let masculine = &self.tokens[*offset..*offset + n];
+            window.iter().zip(masculine).any(|(feminine, masculine)| feminine.text != masculine.text)
+        });
+        self.tokens.windows(n).enumerate().chain(feminine)
+    }
+
+    // Vrai si le token du flux féminin à cette position diffère du flux masculin
+    fn is_feminine_variant(&self, index: usize) -> bool {
+        self.feminine_tokens
+            .get(index)
+            .is_some_and(|feminine| feminine.text != self.tokens[index].text)
+    }
+
+    // Suites de tokens retenus par `keep`, coupées par la ponctuation : celles
+    // du flux masculin, puis celles du flux féminin qui comptent une forme féminine
+    fn token_runs(&self, keep: impl Fn(&Token) -> bool) -> Vec<Vec<&Token>> {
+        let mut runs = Vec::new();
+        for (stream, feminine) in [(&self.tokens, false), (&self.feminine_tokens, true)] {
+            let mut current: Vec<&Token> = Vec::new();
+            let mut variant = false;
+            let mut previous_end = 0;
+            for (index, token) in stream.iter().enumerate() {
+                if !keep(token) || self.separated_by_punctuation(previous_end, token.start) {
+                    if !current.is_empty() && (!feminine || variant) {
+                        runs.push(std::mem::take(&mut current));
+                    }
+                    current.clear();
+                    variant = false;
+                }
+                if keep(token) {
+                    current.push(token);
+                    variant |= self.is_feminine_variant(index);
+                }
+                previous_end = token.end;
+            }
+            if !current.is_empty() && (!feminine || variant) {
+                runs.push(current);
+            }
+        }
+        runs
+    }
+
+    /// Rewrites the gender-inclusive forms of the content ("enseignant.e.s",
+    /// "étudiant(e)s") with middle dots, so that every tokenizer keeps them
+    /// as one word. Does nothing when inclusive writing handling is off.
+    pub fn normalize_inclusive_writing(&mut self) {
+        if self.inclusive_mode != InclusiveMode::Off {
+            self.content = canonicalize_inclusive(&self.content);
+        }
+    }
+
+    // Vrai pour un token qui sépare les expressions : mot vide ou entité exclue
+    fn is_stop_token(&self, token: &Token) -> bool {
+        self.is_stop_word(token)
+            || (self.entity_mode == EntityMode::Exclude && token.entity.is_some())
+    }
+
+    // Vrai si la forme d'origine du token (en minuscules si la casse est ignorée) est un mot vide
+    fn is_stop_word(&self, token: &Token) -> bool {
+        if self.case_fold {
+            self.ban_list.contains(&token.form().to_lowercase())
+        } else {
+            self.ban_list.contains(token.form())
+        }
+    }
+
+    /// Counts words case-insensitively.
+    ///
+    /// The content keeps its original casing: tokens are keyed in lowercase
+    /// and their written form ("Paris", "CAPES") is kept as surface form.
     pub fn analyze(&mut self) {
-        self.content = self.content.to_lowercase();
+        self.case_fold = true;
+        self.tokenized_content = None;
+    }
+
+    /// Keyword-in-context lines for every occurrence of an expression.
+    ///
+    /// `keys` are the normalized tokens of the expression, as found in
+    /// `tokens()`. Each line holds up to `context` tokens on each side, with
+    /// the text between them (punctuation included) taken from the content.
+    pub fn concordance(&mut self, keys: &[String], context: usize) -> Vec<ConcordanceLine> {
+        self.refresh_tokens();
+        if keys.is_empty() || self.tokens.len() < keys.len() {
+            return Vec::new();
+        }
+
+        let mut lines = Vec::new();
+        for (offset, window) in self.token_windows(keys.len()) {
+            if !window.iter().zip(keys).all(|(token, key)| token.text == *key) {
+                continue;
+            }
+
+            let first = &window[0];
+            let last = &window[window.len() - 1];
+            let last_index = offset + keys.len() - 1;
+            let left_start = self.tokens[offset.saturating_sub(context)].start.min(first.start);
+            let right_index = last_index.saturating_add(context).min(self.tokens.len() - 1);
+            let right_end = self.tokens[right_index].end.max(last.end);
+            lines.push(ConcordanceLine {
+                left: self.content[left_start..first.start].trim().to_string(),
+                keyword: self.content[first.start..last.end].to_string(),
+                right: self.content[last.end..right_end].trim().to_string(),
+                token_offset: offset,
+            });
+        }
+        // Les occurrences au féminin suivent celles du flux masculin
+        lines.sort_by_key(|line| line.token_offset);
+        lines
+    }
+
+    /// Applies a Unicode normalization form to the content and removes
+    /// invisible characters, so that "e" + combining accent and "é" are
+    /// the same word.
+    pub fn normalize_unicode(&mut self, form: UnicodeForm) {
+        self.content = normalize_unicode(&self.content, form);
     }
 
     /// Removes special characters from the analyzer's content.
@@ -64,6 +552,7 @@ impl TextAnalyzer {
     /// This function iterates over each word in the content, filtering characters
     /// to keep only those that are alphanumeric or surrounded by alphanumeric characters.
     /// This allows preserving characters such as apostrophes in contractions or hyphens in compound words.
+    /// The filter is the one applied by [`WhitespaceTokenizer`].
     ///
     /// # Examples
     ///
@@ -75,35 +564,12 @@ impl TextAnalyzer {
     ///
     /// This function does not return anything. It directly modifies `self.content`
     /// by replacing the original content with the filtered version.
+    #[allow(dead_code)]
     pub fn remove_special_characters(&mut self) {
-        self.content = self
-            .content
-            .split_whitespace()
-            .filter_map(|word: &str| {
-                let filtered: String = word
-                    .chars()
-                    .enumerate()
-                    .filter(|&(i, c)| {
-                        c.is_alphanumeric()
-                            || (i > 0
-                                && i < word.len() - 1
-                                && word
-                                    .chars()
-                                    .nth(i - 1)
-                                    .map_or(false, |prev| prev.is_alphanumeric())
-                                && word
-                                    .chars()
-                                    .nth(i + 1)
-                                    .map_or(false, |next| next.is_alphanumeric()))
-                    })
-                    .map(|(_, c)| c)
-                    .collect();
-                if filtered.is_empty() {
-                    None
-                } else {
-                    Some(filtered)
-                }
-            })
+        self.content = WhitespaceTokenizer
+            .tokenize(&self.content)
+            .into_iter()
+            .map(|token| token.text)
             .collect::<Vec<String>>()
             .join(" ");
     }
@@ -111,112 +577,142 @@ impl TextAnalyzer {
     pub fn normalize_apostrophes(&mut self) {
         self.content = self
             .content
-            .replace('\u{2019}', "'") // Remplace l'apostrophe typographique par l'apostrophe simple
-            .replace('\u{2018}', "'") // Remplace l'apostrophe simple gauche
-            .replace('\u{201B}', "'") // Remplace l'apostrophe simple haute
+            // Apostrophe typographique, apostrophe simple gauche et apostrophe simple haute
+            .replace(['\u{2019}', '\u{2018}', '\u{201B}'], "'")
     }
 
+    /// Removes elided articles and pronouns ("l'école" gives "école") and
+    /// expands contractions ("don't" gives "do not"), following the elision
+    /// rules of the language. Whitespace between words is preserved.
     pub fn clean_word(&mut self) {
-        let prefixes_to_remove = [
-            "l'", "d'", "n'", "j'", "s'", "c'", "t'", "l'", "d'", "n'", "j'", "s'", "c'", "t'",
-        ];
-
-        self.content = self
+        let mut cleaned = String::with_capacity(self.content.len());
+        let mut last = 0;
+        for (start, word) in self
             .content
-            .split_whitespace()
-            .map(|word| {
-                let mut cleaned = word.to_string();
-                for prefix in &prefixes_to_remove {
-                    if cleaned.starts_with(prefix) {
-                        cleaned = cleaned[prefix.len()..].to_string();
-                        break;
-                    }
+            .split(char::is_whitespace)
+            .filter(|word| !word.is_empty())
+            .map(|word| (word.as_ptr() as usize - self.content.as_ptr() as usize, word))
+        {
+            cleaned.push_str(&self.content[last..start]);
+            // La ponctuation d'ouverture (parenthèse, guillemet) précède l'article,
+            // la ponctuation finale suit la contraction
+            match (
+                word.find(char::is_alphanumeric),
+                word.rfind(char::is_alphanumeric),
+            ) {
+                (Some(first), Some(last_char)) => {
+                    let end =
+                        last_char + word[last_char..].chars().next().map_or(0, char::len_utf8);
+                    cleaned.push_str(&word[..first]);
+                    cleaned.push_str(&self.elision_rules.apply(&word[first..end]));
+                    cleaned.push_str(&word[end..]);
                 }
-                cleaned
-            })
-            .collect::<Vec<String>>()
-            .join(" ");
+                _ => cleaned.push_str(word),
+            }
+            last = start + word.len();
+        }
+        cleaned.push_str(&self.content[last..]);
+        self.content = cleaned;
     }
 
     pub fn word_frequency_ngrams(&mut self, n: usize) {
-        let words: Vec<&str> = self.content.split_whitespace().collect();
-        if words.len() < n {
+        if n == 0 {
             return;
         }
 
-        let ngram_map = match n {
-            1 => &mut self.word_frequency,
-            2 => &mut self.word_frequency_twograms,
-            3 => &mut self.word_frequency_trigrams,
-            4 => &mut self.word_frequency_fourgrams,
-            5 => &mut self.word_frequency_fivegrams,
-            _ => {
-                println!("Taille de n-gramme non prise en charge : {}", n);
-                return;
-            }
-        };
+        self.refresh_tokens();
+        if self.tokens.len() < n {
+            return;
+        }
 
-        ngram_map.clear();
-        let mut all_ngrams = HashMap::new();
+        // Générer tous les n-grammes possibles, en ne retenant que ceux qui
+        // respectent la politique de mots vides
+        let mut all_ngrams: HashMap<String, usize> = HashMap::new();
+        let mut ngram_map: HashMap<String, usize> = HashMap::new();
+        let mut forms: SurfaceForms = HashMap::new();
+        for (offset, window) in self.token_windows(n) {
+            let ngram = window
+                .iter()
+                .map(|token| token.text.as_str())
+                .collect::<Vec<&str>>()
+                .join(" ");
+            *all_ngrams.entry(ngram.clone()).or_insert(0) += 1;
 
-        // Générer tous les n-grammes possibles
-        if n == 1 {
-            for word in words {
-                *all_ngrams.entry(word.to_string()).or_insert(0) += 1;
+            if !self.is_valid_ngram(offset, window) {
+                continue;
             }
-        } else {
-            for i in 0..=words.len() - n {
-                let window = &words[i..i + n];
-                let ngram = window.join(" ");
-                *all_ngrams.entry(ngram).or_insert(0) += 1;
+
+            if self.stemmer.is_some() || self.fold_diacritics || self.case_fold {
+                let form = window
+                    .iter()
+                    .map(|token| token.form())
+                    .collect::<Vec<&str>>()
+                    .join(" ");
+                *forms
+                    .entry(ngram.clone())
+                    .or_default()
+                    .entry(form)
+                    .or_insert(0) += 1;
             }
+            *ngram_map.entry(ngram).or_insert(0) += 1;
         }
 
         // Sauvegarder le nombre d'expressions uniques (avant filtrage)
         self.unique_expressions.insert(n, all_ngrams.len());
 
-        // Appliquer le filtre de la blacklist
-        for (ngram, count) in all_ngrams {
-            let is_valid = if n == 1 {
-                !self.ban_list.contains(&ngram)
-            } else {
-                let words: Vec<&str> = ngram.split_whitespace().collect();
-                !self.ban_list.contains(&words[0].to_string())
-                    && !self.ban_list.contains(&words[n - 1].to_string())
-            };
-
-            if is_valid {
-                *ngram_map.entry(ngram).or_insert(0) = count;
-            }
-        }
-
         // Sauvegarder le nombre d'expressions retenues (après filtrage)
         self.retained_expressions.insert(n, ngram_map.len());
 
         // Calculer les pourcentages
         let total_words = self.word_count as f64;
-        match n {
-            1 => {
-                self.word_frequency_percentage =
-                    self.calculate_percentages(&self.word_frequency, total_words)
-            }
-            2 => {
-                self.word_frequency_twograms_percentage =
-                    self.calculate_percentages(&self.word_frequency_twograms, total_words)
-            }
-            3 => {
-                self.word_frequency_trigrams_percentage =
-                    self.calculate_percentages(&self.word_frequency_trigrams, total_words)
+        let percentage_map = self.calculate_percentages(&ngram_map, total_words);
+        self.ngram_frequencies.insert(n, ngram_map);
+        self.ngram_percentages.insert(n, percentage_map);
+        self.ngram_forms.insert(n, forms);
+    }
+
+    fn is_valid_ngram(&self, offset: usize, window: &[Token]) -> bool {
+        if !self.protected_terms.is_empty() {
+            let form = window
+                .iter()
+                .map(|token| token.form())
+                .collect::<Vec<&str>>()
+                .join(" ")
+                .to_lowercase();
+            if self.protected_terms.contains(&form) {
+                return true;
             }
-            4 => {
-                self.word_frequency_fourgrams_percentage =
-                    self.calculate_percentages(&self.word_frequency_fourgrams, total_words)
+        }
+
+        let is_stop_word = |token: &Token| self.is_stop_word(token);
+        if self.drop_numeric_tokens && window.iter().any(is_numeric_token) {
+            return false;
+        }
+        if self.sentence_bounded && !self.same_sentence(window[0].start, window[window.len() - 1].start) {
+            return false;
+        }
+        let excluded_entity = |token: &Token| token.entity.is_some();
+        if self.entity_mode == EntityMode::Exclude && window.iter().any(excluded_entity) {
+            return false;
+        }
+        if let Some(patterns) = self.pos_patterns.get(&window.len()) {
+            if let Some(tags) = self.pos_tags.get(offset..offset + window.len()) {
+                if !patterns.is_empty() && !patterns.iter().any(|pattern| pattern.matches(tags)) {
+                    return false;
+                }
             }
-            5 => {
-                self.word_frequency_fivegrams_percentage =
-                    self.calculate_percentages(&self.word_frequency_fivegrams, total_words)
+        }
+        if is_stop_word(&window[0]) || is_stop_word(&window[window.len() - 1]) {
+            return false;
+        }
+
+        match self.stop_word_policy {
+            StopWordPolicy::Boundary => true,
+            StopWordPolicy::NoStopWords => !window.iter().any(is_stop_word),
+            StopWordPolicy::MaxRatio { max_ratio } => {
+                let stop_words = window.iter().filter(|token| is_stop_word(token)).count();
+                stop_words as f64 / window.len() as f64 <= max_ratio
             }
-            _ => {}
         }
     }
 
@@ -234,8 +730,33 @@ impl TextAnalyzer {
             .collect()
     }
 
+    /// Keeps n-grams within a sentence, so the fragments of a zone (two headings,
+    /// two links) are never joined.
+    pub fn set_sentence_bounded(&mut self, bounded: bool) {
+        self.sentence_bounded = bounded;
+    }
+
+    /// Attaches the text of a page zone, prepared with the same settings as the content.
+    pub fn add_zone(&mut self, zone: Zone, mut analyzer: TextAnalyzer) {
+        analyzer.set_sentence_bounded(true);
+        self.zones.push((zone, analyzer));
+    }
+
+    /// Returns, for each retained expression of size `n`, its occurrences in each zone.
+    pub fn zone_frequencies(&mut self, n: usize) -> HashMap<String, ZoneCounts> {
+        let mut frequencies: HashMap<String, ZoneCounts> = HashMap::new();
+        for (zone, analyzer) in &mut self.zones {
+            analyzer.word_frequency_ngrams(n);
+            let Some(counts) = analyzer.ngram_frequencies.get(&n) else { continue };
+            for (ngram, &count) in counts {
+                *frequencies.entry(ngram.clone()).or_default().entry(*zone).or_insert(0) += count;
+            }
+        }
+        frequencies
+    }
+
     pub fn count_words(&mut self) -> usize {
-        let count = self.content.split_whitespace().count();
+        let count = self.tokens().len();
         self.word_count = count;
         count
     }
@@ -246,8 +767,10 @@ impl TextAnalyzer {
         let total_words = self.word_count;
 
         self.average_word_length = 0.0;
-        for (word, &frequency) in &self.word_frequency {
-            total_length += word.len() as f64 * frequency as f64;
+        if let Some(word_frequency) = self.ngram_frequencies.get(&1) {
+            for (word, &frequency) in word_frequency {
+                total_length += word.len() as f64 * frequency as f64;
+            }
         }
         self.average_word_length = if total_words > 0 {
             total_length / total_words as f64
@@ -258,23 +781,32 @@ impl TextAnalyzer {
     }
 
     pub fn filter_banned_words(&mut self) {
-        let words: Vec<&str> = self.content.split_whitespace().collect();
-        self.content = words
-            .into_iter()
-            .filter(|word| !self.ban_list.contains(*word))
+        self.refresh_tokens();
+        self.content = self
+            .tokens
+            .iter()
+            .filter(|token| !self.ban_list.contains(&token.text))
+            .map(|token| token.text.as_str())
             .collect::<Vec<&str>>()
             .join(" ");
     }
 
     pub fn _longest_sentences(&mut self, n: usize) -> &[String] {
-        let sentences: Vec<&str> = self.content.split_inclusive(&['.', '!', '?']).collect();
-        self.longest_sentences = sentences
-            .into_iter()
-            .map(|s| s.trim().split_whitespace().collect::<Vec<&str>>().join(" "))
-            .filter(|s| !s.is_empty() && s.split_whitespace().count() > 3)
+        self.refresh_tokens();
+        self.longest_sentences = self
+            .sentences
+            .iter()
+            .map(|span| {
+                self.content[span.clone()]
+                    .split_whitespace()
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            })
+            .filter(|s| s.split_whitespace().count() > 3)
             .collect();
 
-        self.longest_sentences.sort_by(|a, b| b.len().cmp(&a.len()));
+        self.longest_sentences
+            .sort_by_key(|s| std::cmp::Reverse(s.len()));
         self.longest_sentences.truncate(n);
         &self.longest_sentences
     }
@@ -314,37 +846,18 @@ impl TextAnalyzer {
     }
 
     pub fn _print_ngram_frequency(&self, n: usize) {
-        let (ngram_map, percentage_map) = match n {
-            1 => (&self.word_frequency, &self.word_frequency_percentage),
-            2 => (
-                &self.word_frequency_twograms,
-                &self.word_frequency_twograms_percentage,
-            ),
-            3 => (
-                &self.word_frequency_trigrams,
-                &self.word_frequency_trigrams_percentage,
-            ),
-            4 => (
-                &self.word_frequency_fourgrams,
-                &self.word_frequency_fourgrams_percentage,
-            ),
-            5 => (
-                &self.word_frequency_fivegrams,
-                &self.word_frequency_fivegrams_percentage,
-            ),
-            _ => {
-                println!("Taille de n-gramme non supportée: {}", n);
-                return;
-            }
+        let Some((ngram_map, percentage_map)) = self._get_ngram_frequency(n) else {
+            println!("N-grammes de taille {} non calculés", n);
+            return;
         };
 
         let gram_name = match n {
-            1 => "Word",
-            2 => "Twogram",
-            3 => "Trigram",
-            4 => "Fourgram",
-            5 => "Fivegram",
-            _ => unreachable!(),
+            1 => "Word".to_string(),
+            2 => "Twogram".to_string(),
+            3 => "Trigram".to_string(),
+            4 => "Fourgram".to_string(),
+            5 => "Fivegram".to_string(),
+            _ => format!("{}-gram", n),
         };
 
         println!("\n{} frequency:", gram_name);
@@ -372,27 +885,219 @@ impl TextAnalyzer {
     pub fn _get_ngram_frequency(
         &self,
         n: usize,
-    ) -> Option<(&HashMap<String, usize>, &HashMap<String, f64>)> {
-        match n {
-            1 => Some((&self.word_frequency, &self.word_frequency_percentage)),
-            2 => Some((
-                &self.word_frequency_twograms,
-                &self.word_frequency_twograms_percentage,
-            )),
-            3 => Some((
-                &self.word_frequency_trigrams,
-                &self.word_frequency_trigrams_percentage,
-            )),
-            4 => Some((
-                &self.word_frequency_fourgrams,
-                &self.word_frequency_fourgrams_percentage,
-            )),
-            5 => Some((
-                &self.word_frequency_fivegrams,
-                &self.word_frequency_fivegrams_percentage,
-            )),
-            _ => None,
+    ) -> Option<NgramFrequency<'_>> {
+        Some((
+            self.ngram_frequencies.get(&n)?,
+            self.ngram_percentages.get(&n)?,
+        ))
+    }
+
+    /// Computes PMI, log-likelihood and t-score for the retained n-grams of size `n`.
+    ///
+    /// `word_frequency_ngrams(n)` must have been called first. Counts include
+    /// stop words, so "professeur des écoles" is compared to the frequency of "des".
+    pub fn collocation_scores(&mut self, n: usize) -> HashMap<String, Association> {
+        self.refresh_tokens();
+        let Some(retained) = self.ngram_frequencies.get(&n).filter(|_| n >= 2) else {
+            return HashMap::new();
+        };
+
+        let count_windows = |size: usize| {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for (_, window) in self.token_windows(size) {
+                let key: Vec<&str> = window.iter().map(|token| token.text.as_str()).collect();
+                *counts.entry(key.join(" ")).or_insert(0) += 1;
+            }
+            counts
+        };
+        let unigrams = count_windows(1);
+        let prefixes = count_windows(n - 1);
+        let ngrams = count_windows(n);
+
+        retained
+            .keys()
+            .map(|ngram| {
+                let words: Vec<&str> = ngram.split(' ').collect();
+                let word_counts: Vec<usize> = words
+                    .iter()
+                    .map(|word| unigrams.get(*word).copied().unwrap_or(0))
+                    .collect();
+                let prefix_count = prefixes.get(&words[..n - 1].join(" ")).copied().unwrap_or(0);
+                let association = Association::compute(
+                    ngrams.get(ngram).copied().unwrap_or(0),
+                    &word_counts,
+                    prefix_count,
+                    word_counts[n - 1],
+                    self.tokens.len(),
+                );
+                (ngram.clone(), association)
+            })
+            .collect()
+    }
+
+    /// Computes the readability of the content from its sentences and words.
+    ///
+    /// Only tokens containing a letter count as words, so numbers do not lower
+    /// the syllables per word.
+    pub fn readability(&mut self, language: Language) -> Readability {
+        self.refresh_tokens();
+        let words: Vec<&str> = self
+            .tokens
+            .iter()
+            .map(|token| token.form())
+            .filter(|word| word.chars().any(char::is_alphabetic))
+            .collect();
+        let syllables = words
+            .iter()
+            .map(|word| count_syllables(word, language))
+            .sum();
+
+        Readability::compute(language, self.sentences.len(), words.len(), syllables)
+    }
+
+    /// Extracts keyphrases with RAKE (Rapid Automatic Keyword Extraction).
+    ///
+    /// Candidate phrases are the runs of tokens between stop words of the
+    /// `ban_list` and punctuation. Each word is scored by its degree divided by
+    /// its frequency, and a phrase by the sum of its word scores. Phrases are
+    /// returned by decreasing score.
+    pub fn rake_keyphrases(&mut self) -> Vec<Keyphrase> {
+        self.refresh_tokens();
+
+        // Découper le flux de tokens en expressions candidates
+        let mut candidates = self.token_runs(|token| !self.is_stop_token(token));
+        candidates.retain(|phrase| phrase.len() <= RAKE_MAX_PHRASE_WORDS);
+
+        // Score des mots : degré (co-occurrences dans les expressions) / fréquence
+        let mut frequency: HashMap<&str, f64> = HashMap::new();
+        let mut degree: HashMap<&str, f64> = HashMap::new();
+        for phrase in &candidates {
+            for token in phrase {
+                *frequency.entry(&token.text).or_insert(0.0) += 1.0;
+                *degree.entry(&token.text).or_insert(0.0) += phrase.len() as f64;
+            }
         }
+
+        let mut keyphrases: HashMap<String, Keyphrase> = HashMap::new();
+        for phrase in &candidates {
+            let score = phrase
+                .iter()
+                .map(|token| degree[token.text.as_str()] / frequency[token.text.as_str()])
+                .sum();
+            add_keyphrase(&mut keyphrases, phrase, score);
+        }
+
+        let mut keyphrases: Vec<Keyphrase> = keyphrases.into_values().collect();
+        keyphrases.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.phrase.cmp(&b.phrase)));
+        keyphrases
+    }
+
+    /// Extracts keyphrases with TextRank.
+    ///
+    /// Tokens that are not stop words form a co-occurrence graph (window of
+    /// `TEXTRANK_WINDOW` words), ranked with PageRank. The top third of the
+    /// keywords are kept, and adjacent keywords in the text are merged into
+    /// phrases scored by the sum of their word ranks.
+    pub fn textrank_keyphrases(&mut self) -> Vec<Keyphrase> {
+        self.refresh_tokens();
+
+        // Graphe de co-occurrence pondéré. Dans le flux féminin, seules les
+        // paires qui comptent une forme féminine s'ajoutent à celles du flux masculin
+        let mut vertices: HashMap<&str, usize> = HashMap::new();
+        let mut pairs: Vec<(&str, &str)> = Vec::new();
+        for (stream, feminine) in [(&self.tokens, false), (&self.feminine_tokens, true)] {
+            let candidates: Vec<(usize, &str)> = stream
+                .iter()
+                .enumerate()
+                .filter(|(_, token)| !self.is_stop_token(token))
+                .map(|(index, token)| (index, token.text.as_str()))
+                .collect();
+            for &(_, word) in &candidates {
+                let next = vertices.len();
+                vertices.entry(word).or_insert(next);
+            }
+            for (i, &(index, word)) in candidates.iter().enumerate() {
+                for &(other_index, other) in candidates.iter().skip(i + 1).take(TEXTRANK_WINDOW - 1) {
+                    if !feminine || self.is_feminine_variant(index) || self.is_feminine_variant(other_index) {
+                        pairs.push((word, other));
+                    }
+                }
+            }
+        }
+        let mut edges: Vec<HashMap<usize, f64>> = vec![HashMap::new(); vertices.len()];
+        for (word, other) in pairs {
+            let (a, b) = (vertices[word], vertices[other]);
+            if a != b {
+                *edges[a].entry(b).or_insert(0.0) += 1.0;
+                *edges[b].entry(a).or_insert(0.0) += 1.0;
+            }
+        }
+
+        // PageRank
+        let weight_sums: Vec<f64> = edges.iter().map(|e| e.values().sum()).collect();
+        let mut ranks = vec![1.0; vertices.len()];
+        for _ in 0..TEXTRANK_MAX_ITERATIONS {
+            let next: Vec<f64> = edges
+                .iter()
+                .map(|neighbours| {
+                    let incoming: f64 = neighbours
+                        .iter()
+                        .map(|(&u, weight)| weight / weight_sums[u] * ranks[u])
+                        .sum();
+                    (1.0 - TEXTRANK_DAMPING) + TEXTRANK_DAMPING * incoming
+                })
+                .collect();
+            let delta = next
+                .iter()
+                .zip(&ranks)
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f64::max);
+            ranks = next;
+            if delta < TEXTRANK_TOLERANCE {
+                break;
+            }
+        }
+
+        // Mots clés retenus : le premier tiers du classement
+        let mut ranked: Vec<(&str, f64)> = vertices
+            .iter()
+            .map(|(&word, &index)| (word, ranks[index]))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+        ranked.truncate(vertices.len().div_ceil(3));
+        let keywords: HashMap<&str, f64> = ranked.into_iter().collect();
+
+        // Fusion des mots clés adjacents dans le texte
+        let runs = self.token_runs(|token| {
+            keywords.contains_key(token.text.as_str()) && !self.is_stop_token(token)
+        });
+
+        let mut keyphrases: HashMap<String, Keyphrase> = HashMap::new();
+        for run in runs {
+            let score = run.iter().map(|token| keywords[token.text.as_str()]).sum();
+            add_keyphrase(&mut keyphrases, &run, score);
+        }
+
+        let mut keyphrases: Vec<Keyphrase> = keyphrases.into_values().collect();
+        keyphrases.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.phrase.cmp(&b.phrase)));
+        keyphrases
+    }
+
+    // Vrai si les deux positions appartiennent à la même phrase du contenu
+    fn same_sentence(&self, first: usize, last: usize) -> bool {
+        let sentence = |position: usize| self.sentences.partition_point(|sentence| sentence.end <= position);
+        sentence(first) == sentence(last)
+    }
+
+    // Vrai si le texte entre deux tokens contient autre chose que des espaces
+    fn separated_by_punctuation(&self, from: usize, to: usize) -> bool {
+        self.content[from..to].chars().any(|c| !c.is_whitespace())
+    }
+
+    /// Returns, for each expression of size `n`, the surface forms merged into it
+    /// by the stemmer. Empty when no stemmer is set.
+    pub fn get_surface_forms(&self, n: usize) -> Option<&SurfaceForms> {
+        self.ngram_forms.get(&n)
     }
 
     pub fn get_total_stats(&self) -> (usize, usize, usize) {
@@ -409,7 +1114,42 @@ impl TextAnalyzer {
     }
 
     pub fn _count_word_frequency(&self, word: &String) -> usize {
-        self.word_frequency.get(word).copied().unwrap_or(0)
+        self.ngram_frequencies
+            .get(&1)
+            .and_then(|word_frequency| word_frequency.get(word))
+            .copied()
+            .unwrap_or(0)
     }
 
 }
+
+// Ajoute une occurrence d'expression clé, avec sa forme écrite
+fn add_keyphrase(keyphrases: &mut HashMap<String, Keyphrase>, tokens: &[&Token], score: f64) {
+    let phrase = tokens
+        .iter()
+        .map(|token| token.text.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let form = tokens
+        .iter()
+        .map(|token| token.form())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let keyphrase = keyphrases.entry(phrase.clone()).or_insert(Keyphrase {
+        phrase,
+        score,
+        occurrences: 0,
+        surface_forms: HashMap::new(),
+    });
+    keyphrase.occurrences += 1;
+    *keyphrase.surface_forms.entry(form).or_insert(0) += 1;
+}
+
+// Vrai pour un nombre seul : token composé uniquement de chiffres et de
+// séparateurs (« 2023 », « 99,99 ») ou entité de type nombre
+fn is_numeric_token(token: &Token) -> bool {
+    if token.entity == Some(EntityKind::Number) {
+        return true;
+    }
+    is_number(&token.text)
+}
@@ -0,0 +1,212 @@
+use serde::Deserialize;
+use std::fmt::Debug;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::entity::EntityKind;
+
+/// A token produced by a [`Tokenizer`], with its byte span in the tokenized text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    // Forme d'origine lorsque `text` a été normalisé (racinisation...)
+    pub form: Option<String>,
+    // Type de l'entité remplacée par ce token (prix, date...)
+    pub entity: Option<EntityKind>,
+}
+
+impl Token {
+    fn new(text: &str, start: usize) -> Self {
+        Token {
+            text: text.to_string(),
+            start,
+            end: start + text.len(),
+            form: None,
+            entity: None,
+        }
+    }
+
+    /// Returns the token as it was before normalization.
+    pub fn form(&self) -> &str {
+        self.form.as_deref().unwrap_or(&self.text)
+    }
+}
+
+/// Splits a text into the token stream consumed by every counting function
+/// of `TextAnalyzer`.
+pub trait Tokenizer: Debug + Send + Sync {
+    fn tokenize(&self, text: &str) -> Vec<Token>;
+}
+
+/// Tokenizers selectable per request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerKind {
+    /// Unicode word boundaries, keeping URLs, emails and hyphenated compounds whole.
+    #[default]
+    Unicode,
+    /// Strict Unicode word boundaries (UAX #29), hyphens always split.
+    WordBoundary,
+    /// Historical behavior: whitespace split plus the special character filter.
+    Whitespace,
+}
+
+impl TokenizerKind {
+    pub fn build(self) -> Box<dyn Tokenizer> {
+        match self {
+            TokenizerKind::Unicode => Box::new(UnicodeTokenizer),
+            TokenizerKind::WordBoundary => Box::new(WordBoundaryTokenizer),
+            TokenizerKind::Whitespace => Box::new(WhitespaceTokenizer),
+        }
+    }
+}
+
+// Pronoms clitiques séparés du verbe par un trait d'union (« allez-vous », « dit-il »)
+const FRENCH_CLITICS: [&str; 20] = [
+    "je", "tu", "il", "elle", "on", "nous", "vous", "ils", "elles", "moi", "toi", "lui", "leur",
+    "le", "la", "les", "en", "y", "ce", "t",
+];
+
+/// Default tokenizer.
+///
+/// Words follow the Unicode word boundaries, so "99,99€" gives "99,99" and
+/// "you're" stays whole. URLs and email addresses are kept as single tokens,
+/// and hyphenated compounds ("porte-monnaie") are joined back unless the
+/// trailing part is a French clitic pronoun ("allez-vous" gives "allez", "vous").
+#[derive(Debug, Default)]
+pub struct UnicodeTokenizer;
+
+impl Tokenizer for UnicodeTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+
+        for (offset, chunk) in whitespace_chunks(text) {
+            let (core_offset, core) = trim_punctuation(chunk);
+            if is_url(core) || is_email(core) {
+                tokens.push(Token::new(core, offset + core_offset));
+                continue;
+            }
+
+            let mut words: Vec<Token> = Vec::new();
+            for (start, word) in chunk.unicode_word_indices() {
+                let joined = words.last().is_some_and(|previous| {
+                    chunk[previous.end..start] == *"-" && !is_clitic(word)
+                });
+                if joined {
+                    let previous = words.last_mut().unwrap();
+                    previous.text = chunk[previous.start..start + word.len()].to_string();
+                    previous.end = start + word.len();
+                } else {
+                    words.push(Token::new(word, start));
+                }
+            }
+
+            tokens.extend(words.into_iter().map(|word| Token {
+                start: word.start + offset,
+                end: word.end + offset,
+                ..word
+            }));
+        }
+
+        tokens
+    }
+}
+
+/// Strict Unicode word segmentation (UAX #29), without any post-processing.
+#[derive(Debug, Default)]
+pub struct WordBoundaryTokenizer;
+
+impl Tokenizer for WordBoundaryTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        text.unicode_word_indices()
+            .map(|(start, word)| Token::new(word, start))
+            .collect()
+    }
+}
+
+/// Splits on whitespace and keeps only alphanumeric characters, or characters
+/// surrounded by alphanumeric characters (apostrophes, hyphens...).
+#[derive(Debug, Default)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        whitespace_chunks(text)
+            .filter_map(|(start, word)| {
+                let filtered: String = word
+                    .chars()
+                    .enumerate()
+                    .filter(|&(i, c)| {
+                        c.is_alphanumeric()
+                            || (i > 0
+                                && i < word.len() - 1
+                                && word
+                                    .chars()
+                                    .nth(i - 1)
+                                    .is_some_and(|prev| prev.is_alphanumeric())
+                                && word
+                                    .chars()
+                                    .nth(i + 1)
+                                    .is_some_and(|next| next.is_alphanumeric()))
+                    })
+                    .map(|(_, c)| c)
+                    .collect();
+                if filtered.is_empty() {
+                    None
+                } else {
+                    Some(Token {
+                        text: filtered,
+                        start,
+                        end: start + word.len(),
+                        form: None,
+                        entity: None,
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
+// Découpe le texte sur les espaces en conservant la position de chaque morceau
+fn whitespace_chunks(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(char::is_whitespace)
+        .filter(|chunk| !chunk.is_empty())
+        .map(move |chunk| (chunk.as_ptr() as usize - text.as_ptr() as usize, chunk))
+}
+
+// Retire la ponctuation qui entoure un morceau (parenthèses, guillemets, point final...)
+fn trim_punctuation(chunk: &str) -> (usize, &str) {
+    let trimmed_start = chunk.trim_start_matches(|c: char| "([{\"'«“‘<".contains(c));
+    let offset = chunk.len() - trimmed_start.len();
+    let core = trimmed_start.trim_end_matches(|c: char| ".,;:!?)]}\"'»”’>".contains(c));
+    (offset, core)
+}
+
+fn is_url(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    ["http://", "https://", "www."]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme) && lower.len() > scheme.len())
+}
+
+fn is_email(word: &str) -> bool {
+    let Some((local, domain)) = word.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && local
+            .chars()
+            .all(|c| c.is_alphanumeric() || "._%+-".contains(c))
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && domain
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-')
+}
+
+fn is_clitic(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    FRENCH_CLITICS.contains(&lower.as_str())
+}
@@ -1,26 +1,98 @@
-use reqwest;
-use scraper::{Html, Selector};
+use ego_tree::{NodeId, NodeRef};
+use scraper::{ElementRef, Html, Node, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::error::Error;
+use std::iter::Peekable;
+
+use crate::links::{extract_links, Link};
+use crate::metadata::{parse_json_ld, parse_microdata, parse_social_tags, schema_types, PageMetadata};
+use crate::zone::Zone;
+
+// Balises dont le texte n'est jamais affiché
+const IGNORED_TAGS: [&str; 5] = ["script", "style", "noscript", "template", "svg"];
+
+// Balises de navigation et d'habillage écartées du contenu principal
+const BOILERPLATE_TAGS: [&str; 8] = ["nav", "header", "footer", "aside", "form", "button", "select", "iframe"];
+
+// Balises dont le texte forme un paragraphe du contenu
+const PARAGRAPH_TAGS: [&str; 4] = ["p", "pre", "td", "blockquote"];
+
+// Indices de classe ou d'identifiant propres au contenu ou à l'habillage
+const POSITIVE_HINTS: [&str; 7] = ["article", "content", "main", "post", "entry", "text", "story"];
+const NEGATIVE_HINTS: [&str; 16] = [
+    "nav", "menu", "footer", "sidebar", "comment", "cookie", "consent", "banner",
+    "share", "social", "promo", "related", "breadcrumb", "newsletter", "popup", "modal",
+];
+
+// Séparateur placé entre deux fragments d'une même zone : il y termine une
+// phrase, que les n-grammes d'une zone ne franchissent pas
+const ZONE_SEPARATOR: &str = " . ";
+
+// Longueur minimale (en caractères) d'un paragraphe pris en compte
+const MIN_PARAGRAPH_LENGTH: usize = 25;
+
+/// How the text of a page is extracted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentExtraction {
+    /// Main content only, scored by text and link density.
+    #[default]
+    Main,
+    /// Every text node under `<body>`.
+    FullBody,
+}
 
 pub struct WebAnalyzer {
     url: String,
+    extraction: ContentExtraction,
 }
 
+// Texte extrait d'une page, langue déclarée par `<html lang>`, texte de chaque zone,
+// plan des titres, métadonnées et liens
 #[derive(Debug)]
-struct PageMetadata {
-    title: String,
-    description: String,
-    keywords: String,
+pub struct WebPage {
+    pub content: String,
+    pub lang: Option<String>,
+    pub zones: HashMap<Zone, String>,
+    pub outline: Vec<Heading>,
+    pub metadata: PageMetadata,
+    pub links: Vec<Link>,
+}
+
+/// Heading of a page, with the lower-level headings placed under it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Heading>,
+}
+
+impl Heading {
+    /// Returns this heading followed by all its descendants, in document order.
+    pub fn flatten(&self) -> Vec<&Heading> {
+        let mut headings = vec![self];
+        for child in &self.children {
+            headings.extend(child.flatten());
+        }
+        headings
+    }
 }
 
 impl WebAnalyzer {
     pub fn new(url: &str) -> Self {
         WebAnalyzer {
             url: url.to_string(),
+            extraction: ContentExtraction::default(),
         }
     }
 
-    pub async fn fetch_and_analyze(&mut self) -> Result<String, Box<dyn Error>> {
+    pub fn set_extraction(&mut self, extraction: ContentExtraction) {
+        self.extraction = extraction;
+    }
+
+    pub async fn fetch_and_analyze(&mut self) -> Result<WebPage, Box<dyn Error>> {
         let client = reqwest::Client::builder()
             .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
             .danger_accept_invalid_certs(true)
@@ -33,24 +105,112 @@ impl WebAnalyzer {
         }
 
         let html = response.text().await?;
-        let document = Html::parse_document(&html);
+        Ok(self.analyze_html(&html))
+    }
+
+    /// Extracts the text, zones and declared language of an HTML page.
+    pub fn analyze_html(&self, html: &str) -> WebPage {
+        let document = Html::parse_document(html);
 
         // Extraire le texte comme dans extract_text_content
         let metadata = self.extract_metadata(&document);
         let mut combined_text = String::new();
 
         combined_text.push_str(&metadata.title);
-        combined_text.push_str(" ");
+        combined_text.push(' ');
         combined_text.push_str(&metadata.description);
-        combined_text.push_str(" ");
+        combined_text.push(' ');
         combined_text.push_str(&metadata.keywords);
-        combined_text.push_str(" ");
+        combined_text.push(' ');
 
-        if let Some(body_content) = self.extract_body_content(&document) {
-            combined_text.push_str(&body_content);
+        // Contenu principal, sinon tout le corps de la page
+        let lengths = TextLengths::measure(document.tree.root());
+        let main_nodes = match self.extraction {
+            ContentExtraction::Main => self.main_content_nodes(&document, &lengths),
+            ContentExtraction::FullBody => None,
+        };
+        let (nodes, main_only) = match main_nodes {
+            Some(nodes) => (nodes, true),
+            None => {
+                let body_selector = Selector::parse("body").unwrap();
+                let body = document.select(&body_selector).next();
+                (body.map(|body| vec![*body]).unwrap_or_default(), false)
+            }
+        };
+
+        let filter = main_only.then_some(&lengths);
+        let mut body_text = String::new();
+        let mut zones: HashMap<Zone, String> = HashMap::new();
+        for &node in &nodes {
+            collect_text(node, filter, &mut body_text);
+            body_text.push(' ');
+            collect_zones(node, filter, Zone::Body, &mut zones);
+        }
+        combined_text.push_str(&squash_whitespace(&body_text));
+
+        // Le texte des liens vient de l'inventaire : les liens de la navigation
+        // et du pied de page comptent aussi dans le profil de liens
+        let links = extract_links(&document, &self.url);
+        let anchor_texts: Vec<&str> = links.iter().map(|link| link.anchor_text.as_str()).collect();
+        zones.insert(Zone::Anchors, anchor_texts.join(ZONE_SEPARATOR));
+        // De même, les intertitres sont ceux du plan, y compris ceux de l'en-tête
+        let outline = self.extract_outline(&document);
+        let heading_texts: Vec<&str> = outline
+            .iter()
+            .flat_map(Heading::flatten)
+            .map(|heading| heading.text.as_str())
+            .collect();
+        zones.insert(Zone::Headings, heading_texts.join(ZONE_SEPARATOR));
+        zones.insert(Zone::Title, metadata.title.clone());
+        zones.insert(Zone::Meta, format!("{} {} {}", metadata.description, ZONE_SEPARATOR, metadata.keywords));
+        let zones = zones
+            .into_iter()
+            .map(|(zone, text)| (zone, squash_whitespace(&text)))
+            .filter(|(_, text)| text.chars().any(char::is_alphanumeric))
+            .collect();
+
+        WebPage {
+            content: combined_text,
+            lang: self.extract_lang(&document),
+            zones,
+            outline,
+            metadata,
+            links,
         }
+    }
+
+    fn extract_lang(&self, document: &Html) -> Option<String> {
+        let html_selector = Selector::parse("html").unwrap();
 
-        Ok(combined_text)
+        document
+            .select(&html_selector)
+            .next()
+            .and_then(|html| html.value().attr("lang").or(html.value().attr("xml:lang")))
+            .map(|lang| lang.trim().to_string())
+            .filter(|lang| !lang.is_empty())
+    }
+
+    // Plan H1–H6 de la page. En mode contenu principal, les titres de la
+    // navigation, du pied de page et des barres latérales sont ignorés
+    fn extract_outline(&self, document: &Html) -> Vec<Heading> {
+        let heading_selector = Selector::parse("body h1, body h2, body h3, body h4, body h5, body h6").unwrap();
+
+        let mut headings = document
+            .select(&heading_selector)
+            .filter(|heading| {
+                self.extraction == ContentExtraction::FullBody
+                    || !heading
+                        .ancestors()
+                        .filter_map(ElementRef::wrap)
+                        .any(|ancestor| ancestor.value().name() != "header" && is_boilerplate(ancestor))
+            })
+            .filter_map(|heading| {
+                let level = heading.value().name()[1..].parse::<u8>().ok()?;
+                let text = inner_text(heading);
+                (!text.is_empty()).then_some((level, text))
+            })
+            .peekable();
+        nest_headings(&mut headings, 0)
     }
 
     fn extract_metadata(&self, document: &Html) -> PageMetadata {
@@ -58,6 +218,11 @@ impl WebAnalyzer {
         let meta_description = Selector::parse("meta[name='description']").unwrap();
         let meta_keywords = Selector::parse("meta[name='keywords']").unwrap();
 
+        let (open_graph, twitter_card) = parse_social_tags(document);
+        let json_ld = parse_json_ld(document);
+        let microdata = parse_microdata(document);
+        let schema_types = schema_types(&json_ld, &microdata);
+
         PageMetadata {
             title: document
                 .select(&title_selector)
@@ -75,45 +240,300 @@ impl WebAnalyzer {
                 .next()
                 .and_then(|m| m.value().attr("content"))
                 .unwrap_or_default()
-                .replace(",", ", ")
-                .to_string(),
+                .replace(",", ", "),
+            open_graph,
+            twitter_card,
+            json_ld,
+            microdata,
+            schema_types,
         }
     }
 
-    fn extract_body_content(&self, document: &Html) -> Option<String> {
+    // Contenu principal à la manière de Readability : les paragraphes sont
+    // notés selon leur longueur, la note remonte à leur parent et grand-parent,
+    // puis le meilleur bloc est retenu avec ses frères de bonne note
+    fn main_content_nodes<'a>(&self, document: &'a Html, lengths: &TextLengths) -> Option<Vec<NodeRef<'a, Node>>> {
         let body_selector = Selector::parse("body").unwrap();
-        let script_selector = Selector::parse("script").unwrap();
-        let style_selector = Selector::parse("style").unwrap();
+        let body = document.select(&body_selector).next()?;
 
-        document.select(&body_selector).next().map(|body| {
-            let mut content = body.html();
+        let mut scores: HashMap<NodeId, f64> = HashMap::new();
+        for node in body.descendants() {
+            let Some(element) = ElementRef::wrap(node) else { continue };
+            if !is_paragraph(element) || has_boilerplate_ancestor(element) {
+                continue;
+            }
+            let text = inner_text(element);
+            let length = text.chars().count();
+            if length < MIN_PARAGRAPH_LENGTH {
+                continue;
+            }
 
-            // Suppression des scripts et styles
-            for script in document.select(&script_selector) {
-                content = content.replace(&script.html(), "");
+            let commas = text.matches([',', '،', '、']).count() as f64;
+            let paragraph_score = 1.0 + commas + (length as f64 / 100.0).min(3.0);
+
+            let parent = element.parent().and_then(ElementRef::wrap);
+            if let Some(parent) = parent {
+                *scores.entry(parent.id()).or_insert_with(|| initial_score(parent)) += paragraph_score;
+                if let Some(grandparent) = parent.parent().and_then(ElementRef::wrap) {
+                    *scores.entry(grandparent.id()).or_insert_with(|| initial_score(grandparent)) +=
+                        paragraph_score / 2.0;
+                }
             }
-            for style in document.select(&style_selector) {
-                content = content.replace(&style.html(), "");
+        }
+
+        // Les blocs composés surtout de liens sont pénalisés
+        let scored: HashMap<NodeId, f64> = scores
+            .into_iter()
+            .map(|(id, score)| (id, score * (1.0 - lengths.link_density(id))))
+            .collect();
+
+        let (&top_id, &top_score) = scored
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))?;
+        let top = document.tree.get(top_id)?;
+
+        // Les frères du meilleur bloc qui portent aussi du contenu sont repris
+        let threshold = (top_score * 0.2).max(10.0);
+        let siblings: Vec<NodeRef<Node>> = match top.parent() {
+            Some(parent) => parent.children().collect(),
+            None => vec![top],
+        };
+
+        let nodes: Vec<NodeRef<Node>> = siblings
+            .into_iter()
+            .filter(|&sibling| {
+                let Some(element) = ElementRef::wrap(sibling) else { return false };
+                sibling.id() == top_id
+                    || scored.get(&sibling.id()).is_some_and(|&score| score >= threshold)
+                    || (element.value().name() == "p" && lengths.is_content_paragraph(sibling.id()))
+            })
+            .collect();
+
+        let mut text = String::new();
+        for &node in &nodes {
+            collect_text(node, Some(lengths), &mut text);
+        }
+        if text.trim().is_empty() {
+            None
+        } else {
+            Some(nodes)
+        }
+    }
+}
+
+// Concatène les nœuds texte. Avec les longueurs de la page, l'habillage et les
+// blocs composés surtout de liens sont écartés
+fn collect_text(node: NodeRef<Node>, filter: Option<&TextLengths>, output: &mut String) {
+    match node.value() {
+        Node::Text(text) => {
+            output.push_str(text);
+            output.push(' ');
+        }
+        Node::Element(element) => {
+            let name = element.name();
+            if IGNORED_TAGS.contains(&name) {
+                return;
+            }
+            if filter.is_some_and(|lengths| lengths.is_boilerplate(node)) {
+                return;
+            }
+            for child in node.children() {
+                collect_text(child, filter, output);
+            }
+        }
+        _ => {
+            for child in node.children() {
+                collect_text(child, filter, output);
             }
+        }
+    }
+}
+
+// Répartit le texte entre le corps, les intertitres, les liens et les textes
+// alternatifs. Le texte des intertitres et des liens est ensuite remplacé par
+// celui du plan et de l'inventaire complet des liens de la page. Les fragments
+// sont séparés par un point pour qu'aucun n-gramme ne relie deux intertitres
+// ou deux liens
+fn collect_zones(node: NodeRef<Node>, filter: Option<&TextLengths>, zone: Zone, zones: &mut HashMap<Zone, String>) {
+    match node.value() {
+        Node::Text(text) => {
+            let buffer = zones.entry(zone).or_default();
+            buffer.push_str(text);
+            buffer.push(' ');
+        }
+        Node::Element(element) => {
+            let name = element.name();
+            if IGNORED_TAGS.contains(&name) {
+                return;
+            }
+            if filter.is_some_and(|lengths| lengths.is_boilerplate(node)) {
+                return;
+            }
+            if name == "img" {
+                if let Some(alt) = element.attr("alt") {
+                    let buffer = zones.entry(Zone::AltText).or_default();
+                    buffer.push_str(alt);
+                    buffer.push_str(ZONE_SEPARATOR);
+                }
+                return;
+            }
+
+            // Un lien dans un intertitre reste un intertitre
+            let inner_zone = match name {
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => Zone::Headings,
+                "a" if zone == Zone::Body => Zone::Anchors,
+                _ => zone,
+            };
+            if inner_zone != zone {
+                zones.entry(zone).or_default().push_str(ZONE_SEPARATOR);
+            }
+            for child in node.children() {
+                collect_zones(child, filter, inner_zone, zones);
+            }
+            if inner_zone != zone {
+                zones.entry(inner_zone).or_default().push_str(ZONE_SEPARATOR);
+            }
+        }
+        _ => {
+            for child in node.children() {
+                collect_zones(child, filter, zone, zones);
+            }
+        }
+    }
+}
+
+// Place sous chaque titre les titres suivants de niveau inférieur
+fn nest_headings(headings: &mut Peekable<impl Iterator<Item = (u8, String)>>, parent_level: u8) -> Vec<Heading> {
+    let mut nested = Vec::new();
+    while let Some((level, text)) = headings.next_if(|(level, _)| *level > parent_level) {
+        let children = nest_headings(headings, level);
+        nested.push(Heading { level, text, children });
+    }
+    nested
+}
+
+fn squash_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn inner_text(element: ElementRef) -> String {
+    let mut text = String::new();
+    collect_text(*element, None, &mut text);
+    squash_whitespace(&text)
+}
 
-            // Extraction du texte propre
-            let clean_document = Html::parse_document(&content);
-            let mut combined_text = String::new();
+// Longueur du texte de chaque élément et de la part située dans des liens,
+// mesurées en un seul parcours de la page plutôt qu'à chaque bloc visité
+#[derive(Debug, Default)]
+struct TextLengths {
+    // Élément -> (longueur du texte, longueur du texte des liens), espaces réduites
+    lengths: HashMap<NodeId, (usize, usize)>,
+}
+
+impl TextLengths {
+    fn measure(root: NodeRef<Node>) -> Self {
+        let mut lengths = TextLengths::default();
+        lengths.visit(root);
+        lengths
+    }
 
-            for node in clean_document.root_element().descendants() {
-                if let Some(text_node) = node.value().as_text() {
-                    combined_text.push_str(text_node);
-                    combined_text.push(' ');
+    // Caractères hors espaces, mots et longueur des liens du sous-arbre : une
+    // fois les espaces réduites, le texte compte un espace entre deux mots
+    fn visit(&mut self, node: NodeRef<Node>) -> (usize, usize, usize) {
+        match node.value() {
+            Node::Text(text) => {
+                let chars = text.chars().filter(|c| !c.is_whitespace()).count();
+                (chars, text.split_whitespace().count(), 0)
+            }
+            Node::Element(element) if IGNORED_TAGS.contains(&element.name()) => (0, 0, 0),
+            value => {
+                let (mut chars, mut words, mut links) = (0, 0, 0);
+                for child in node.children() {
+                    let (child_chars, child_words, child_links) = self.visit(child);
+                    chars += child_chars;
+                    words += child_words;
+                    links += child_links;
                 }
+                // Les liens comptés sont ceux que contient l'élément, pas l'élément lui-même
+                if let Node::Element(element) = value {
+                    let length = chars + words.saturating_sub(1);
+                    self.lengths.insert(node.id(), (length, links));
+                    if element.name() == "a" {
+                        links += length;
+                    }
+                }
+                (chars, words, links)
             }
+        }
+    }
 
-            combined_text
-                .replace('\n', " ")
-                .replace('\t', " ")
-                .split_whitespace()
-                .collect::<Vec<_>>()
-                .join(" ")
-        })
+    // Part du texte d'un bloc située dans des liens
+    fn link_density(&self, id: NodeId) -> f64 {
+        match self.lengths.get(&id) {
+            Some(&(length, link_length)) if length > 0 => (link_length as f64 / length as f64).min(1.0),
+            _ => 0.0,
+        }
+    }
+
+    // Habillage de la page, ou bloc composé surtout de liens
+    fn is_boilerplate(&self, node: NodeRef<Node>) -> bool {
+        ElementRef::wrap(node).is_some_and(|element| is_boilerplate(element) || self.link_density(node.id()) > 0.5)
     }
 
+    fn is_content_paragraph(&self, id: NodeId) -> bool {
+        self.lengths.get(&id).is_some_and(|&(length, _)| length > 80) && self.link_density(id) < 0.25
+    }
+}
+
+// Poids tiré des classes et de l'identifiant : +25 pour un indice de contenu, -25 pour un indice d'habillage
+fn class_weight(element: ElementRef) -> f64 {
+    let mut weight = 0.0;
+    for attribute in ["class", "id"] {
+        let Some(value) = element.value().attr(attribute) else { continue };
+        let value = value.to_lowercase();
+        if NEGATIVE_HINTS.iter().any(|hint| value.contains(hint)) {
+            weight -= 25.0;
+        } else if POSITIVE_HINTS.iter().any(|hint| value.contains(hint)) {
+            weight += 25.0;
+        }
+    }
+    weight
+}
+
+fn initial_score(element: ElementRef) -> f64 {
+    let tag_score = match element.value().name() {
+        "article" | "main" => 10.0,
+        "div" | "section" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "ol" | "ul" | "dl" | "address" | "form" => -3.0,
+        "th" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => -5.0,
+        _ => 0.0,
+    };
+    tag_score + class_weight(element)
+}
+
+fn is_boilerplate(element: ElementRef) -> bool {
+    BOILERPLATE_TAGS.contains(&element.value().name())
+        || element.value().attr("role").is_some_and(|role| role == "navigation" || role == "banner")
+        || class_weight(element) < 0.0
+}
+
+fn has_boilerplate_ancestor(element: ElementRef) -> bool {
+    element.ancestors().filter_map(ElementRef::wrap).any(is_boilerplate)
+}
+
+// Un paragraphe, ou un bloc qui porte lui-même du texte sans sous-blocs
+fn is_paragraph(element: ElementRef) -> bool {
+    let name = element.value().name();
+    if PARAGRAPH_TAGS.contains(&name) {
+        return true;
+    }
+    if name != "div" {
+        return false;
+    }
+    let has_block_child = element
+        .children()
+        .filter_map(ElementRef::wrap)
+        .any(|child| matches!(child.value().name(), "div" | "p" | "ul" | "ol" | "table" | "section" | "article" | "pre" | "blockquote"));
+    !has_block_child
 }
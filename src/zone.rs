@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Part of a page a piece of text comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Zone {
+    /// `<title>`
+    Title,
+    /// `<h1>` to `<h6>`
+    Headings,
+    /// Meta description and keywords
+    Meta,
+    /// Text outside headings and links
+    Body,
+    /// Text of every link of the page, navigation included
+    Anchors,
+    /// `alt` attribute of images
+    AltText,
+}
+
+impl Zone {
+    // Poids par défaut : un terme du titre ou d'un intertitre pèse plus qu'un terme du corps
+    fn default_weight(self) -> f64 {
+        match self {
+            Zone::Title => 3.0,
+            Zone::Headings => 2.0,
+            Zone::Meta => 1.5,
+            Zone::Body => 1.0,
+            Zone::Anchors => 1.0,
+            Zone::AltText => 0.5,
+        }
+    }
+}
+
+// Occurrences d'une expression dans chaque zone
+pub type ZoneCounts = HashMap<Zone, usize>;
+
+/// Weight of each zone in the zone score, with per-request overrides.
+#[derive(Debug, Clone, Default)]
+pub struct ZoneWeights {
+    overrides: HashMap<Zone, f64>,
+}
+
+impl ZoneWeights {
+    pub fn new(overrides: HashMap<Zone, f64>) -> Self {
+        ZoneWeights { overrides }
+    }
+
+    pub fn weight(&self, zone: Zone) -> f64 {
+        self.overrides
+            .get(&zone)
+            .copied()
+            .unwrap_or_else(|| zone.default_weight())
+    }
+
+    /// Sum of the occurrences in each zone, multiplied by the zone weight.
+    pub fn score(&self, counts: &ZoneCounts) -> f64 {
+        counts
+            .iter()
+            .map(|(&zone, &count)| self.weight(zone) * count as f64)
+            .sum()
+    }
+}